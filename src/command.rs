@@ -1,18 +1,26 @@
 //! Controller commands
+//!
+//! Typed commands can be sent with [`ST7567S::send_command`](crate::display::ST7567S::send_command)
+//! to access controller features not wrapped by the driver.
+//! Note that the driver doesn't track changes made this way,
+//! e.g. changing SEG direction affects the columns used by the driver
 
-use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use crate::{consts::MAX_COMMANDS_LEN, interface::ControllerInterface};
+use display_interface::DisplayError;
 
 /// ST7567S command representation\
 /// All commands are 8-bit long\
 /// Some commands have data bytes following them\
-pub(crate) trait Command {
+pub trait Command {
+    /// Command byte
     fn command(&self) -> u8;
+    /// Optional data byte following the command byte
     fn data(&self) -> Option<u8> {
         None
     }
 
     /// Write command data to display
-    fn write(&self, display_interface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
+    fn write(&self, display_interface: &mut impl ControllerInterface) -> Result<(), DisplayError> {
         let mut bytes = [0; 2];
         let mut len = 1;
         bytes[0] = self.command();
@@ -21,18 +29,129 @@ pub(crate) trait Command {
             len = 2;
         }
 
-        display_interface.send_commands(DataFormat::U8(&bytes[..len]))?;
+        display_interface.write_commands(&bytes[..len])?;
 
         Ok(())
     }
 }
 
+/// Stack-allocated buffer collecting up to `N` command bytes to send them in a single bus transaction
+///
+/// Long buffers are sent in several transactions accepted by every interface, without splitting commands
+pub struct CommandBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    /// Length of every pushed command including its data byte, so commands are never split
+    command_lens: [u8; N],
+    commands: usize,
+}
+
+impl<const N: usize> CommandBuffer<N> {
+    /// Create empty buffer
+    pub fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+            command_lens: [0; N],
+            commands: 0,
+        }
+    }
+
+    /// Append command to the buffer
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if there is no space left for the command
+    pub fn push(&mut self, command: &impl Command) -> Result<(), DisplayError> {
+        let len = if command.data().is_some() { 2 } else { 1 };
+        if self.len + len > N {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        self.bytes[self.len] = command.command();
+        if let Some(data) = command.data() {
+            self.bytes[self.len + 1] = data;
+        }
+        self.len += len;
+        self.command_lens[self.commands] = len as u8;
+        self.commands += 1;
+
+        Ok(())
+    }
+
+    /// Collected command bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Send collected commands to display
+    pub fn write(
+        &self,
+        display_interface: &mut impl ControllerInterface,
+    ) -> Result<(), DisplayError> {
+        write_command_chunks(
+            display_interface,
+            self.as_bytes(),
+            self.command_lens[..self.commands]
+                .iter()
+                .map(|&len| len as usize),
+        )
+    }
+}
+
+impl<const N: usize> Default for CommandBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Send command bytes in chunks accepted by all interfaces at once, see [`MAX_COMMANDS_LEN`]
+///
+/// `command_lens` are the lengths of the consecutive commands, which are never split between chunks
+pub(crate) fn write_command_chunks(
+    display_interface: &mut impl ControllerInterface,
+    mut commands: &[u8],
+    command_lens: impl IntoIterator<Item = usize>,
+) -> Result<(), DisplayError> {
+    let mut len = 0;
+    for command_len in command_lens {
+        if len > 0 && len + command_len > MAX_COMMANDS_LEN {
+            let (chunk, rest) = commands.split_at(len);
+            display_interface.write_commands(chunk)?;
+            commands = rest;
+            len = 0;
+        }
+        len += command_len;
+    }
+
+    if !commands.is_empty() {
+        display_interface.write_commands(commands)?;
+    }
+
+    Ok(())
+}
+
+/// Lengths of the commands in raw command bytes, detected by their command bytes
+pub(crate) fn raw_command_lens(mut commands: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    core::iter::from_fn(move || {
+        let len = command_len(*commands.first()?).min(commands.len());
+        commands = &commands[len..];
+        Some(len)
+    })
+}
+
+/// Length of the command starting with given command byte, including its data byte
+fn command_len(command: u8) -> usize {
+    match command {
+        // Set electronic volume and set booster
+        0x81 | 0xf8 => 2,
+        _ => 1,
+    }
+}
+
 // Commands implementation
 // Reference: https://www.buydisplay.com/download/ic/ST7567S.pdf, chapter 9
 
 /// Select bias setting: 1/9 or 1/7
-#[allow(dead_code)]
-pub(crate) enum SetBiasCommand {
+pub enum SetBiasCommand {
     Bias1_7,
     Bias1_9,
 }
@@ -47,8 +166,7 @@ impl Command for SetBiasCommand {
 }
 
 /// Set scan direction of SEG
-#[allow(dead_code)]
-pub(crate) enum SetSEGDirectionCommand {
+pub enum SetSEGDirectionCommand {
     Normal,
     Reverse,
 }
@@ -63,8 +181,7 @@ impl Command for SetSEGDirectionCommand {
 }
 
 /// Set output direction of COM
-#[allow(dead_code)]
-pub(crate) enum SetCOMDirectionCommand {
+pub enum SetCOMDirectionCommand {
     Normal,
     Reverse,
 }
@@ -79,8 +196,7 @@ impl Command for SetCOMDirectionCommand {
 }
 
 /// Select regulation resistor ratio
-#[allow(dead_code)]
-pub(crate) enum SetRegulationResistorRatioCommand {
+pub enum SetRegulationResistorRatioCommand {
     Ratio3_0,
     Ratio3_5,
     Ratio4_0,
@@ -107,13 +223,13 @@ impl Command for SetRegulationResistorRatioCommand {
 }
 
 /// Set electronic volume (EV) level
-pub(crate) struct SetElectronicVolumeCommand {
+pub struct SetElectronicVolumeCommand {
     /// EV level (0-63)
     level: u8,
 }
 
 impl SetElectronicVolumeCommand {
-    pub(crate) fn new(level: u8) -> Option<Self> {
+    pub fn new(level: u8) -> Option<Self> {
         if level > 63 {
             None
         } else {
@@ -132,30 +248,59 @@ impl Command for SetElectronicVolumeCommand {
 }
 
 /// Set Power Control
-pub(crate) enum SetPowerControlCommand {
-    BoosterOn,
-    VoltageRegulatorOn,
-    VoltageFollowerOn,
+pub struct SetPowerControlCommand {
+    /// Built-in booster (VB)
+    booster: bool,
+    /// Built-in voltage regulator (VR)
+    regulator: bool,
+    /// Built-in voltage follower (VF)
+    follower: bool,
+}
+
+impl SetPowerControlCommand {
+    pub fn new(booster: bool, regulator: bool, follower: bool) -> Self {
+        Self {
+            booster,
+            regulator,
+            follower,
+        }
+    }
 }
 
 impl Command for SetPowerControlCommand {
     fn command(&self) -> u8 {
+        0x28 | (u8::from(self.booster) << 2)
+            | (u8::from(self.regulator) << 1)
+            | u8::from(self.follower)
+    }
+}
+
+/// Select booster level
+pub enum SetBoosterRatioCommand {
+    Ratio4x,
+    Ratio5x,
+}
+
+impl Command for SetBoosterRatioCommand {
+    fn command(&self) -> u8 {
+        0xf8
+    }
+    fn data(&self) -> Option<u8> {
         match self {
-            SetPowerControlCommand::BoosterOn => 0x2c,
-            SetPowerControlCommand::VoltageRegulatorOn => 0x2e,
-            SetPowerControlCommand::VoltageFollowerOn => 0x2f,
+            SetBoosterRatioCommand::Ratio4x => Some(0x00),
+            SetBoosterRatioCommand::Ratio5x => Some(0x01),
         }
     }
 }
 
 /// Set start line
-pub(crate) struct SetStartLineCommand {
+pub struct SetStartLineCommand {
     /// Start line (0-63)
     line: u8,
 }
 
 impl SetStartLineCommand {
-    pub(crate) fn new(line: u8) -> Option<Self> {
+    pub fn new(line: u8) -> Option<Self> {
         if line > 63 {
             None
         } else {
@@ -171,14 +316,14 @@ impl Command for SetStartLineCommand {
 }
 
 /// Set page address
-pub(crate) struct SetPageAddressCommand {
-    /// Page address (0-7)
+pub struct SetPageAddressCommand {
+    /// Page address (0-8), page 8 contains only the icon line
     address: u8,
 }
 
 impl SetPageAddressCommand {
-    pub(crate) fn new(address: u8) -> Option<Self> {
-        if address > 7 {
+    pub fn new(address: u8) -> Option<Self> {
+        if address > 8 {
             None
         } else {
             Some(Self { address })
@@ -193,13 +338,13 @@ impl Command for SetPageAddressCommand {
 }
 
 /// Set Column address
-pub(crate) struct SetColumnAddressLSNibbleCommand {
+pub struct SetColumnAddressLSNibbleCommand {
     /// Column address (0-131)
     address: u8,
 }
 
 impl SetColumnAddressLSNibbleCommand {
-    pub(crate) fn new(address: u8) -> Option<Self> {
+    pub fn new(address: u8) -> Option<Self> {
         if address > 131 {
             None
         } else {
@@ -210,17 +355,18 @@ impl SetColumnAddressLSNibbleCommand {
 
 impl Command for SetColumnAddressLSNibbleCommand {
     fn command(&self) -> u8 {
-        0x00 | (self.address & 0x0f)
+        self.address & 0x0f
     }
 }
 
-pub(crate) struct SetColumnAddressMSNibbleCommand {
+/// Set Column address
+pub struct SetColumnAddressMSNibbleCommand {
     /// Column address (0-131)
     address: u8,
 }
 
 impl SetColumnAddressMSNibbleCommand {
-    pub(crate) fn new(address: u8) -> Option<Self> {
+    pub fn new(address: u8) -> Option<Self> {
         if address > 131 {
             None
         } else {
@@ -236,8 +382,7 @@ impl Command for SetColumnAddressMSNibbleCommand {
 }
 
 /// Set display on/off
-#[allow(dead_code)]
-pub(crate) enum DisplayOnCommand {
+pub enum DisplayOnCommand {
     On,
     Off,
 }
@@ -251,8 +396,67 @@ impl Command for DisplayOnCommand {
     }
 }
 
+/// Set inverse display
+pub enum InverseDisplayCommand {
+    Normal,
+    Inverse,
+}
+
+impl Command for InverseDisplayCommand {
+    fn command(&self) -> u8 {
+        match self {
+            InverseDisplayCommand::Normal => 0xa6,
+            InverseDisplayCommand::Inverse => 0xa7,
+        }
+    }
+}
+
+/// Set all pixels on regardless of the display memory content
+pub enum AllPixelsOnCommand {
+    Normal,
+    AllOn,
+}
+
+impl Command for AllPixelsOnCommand {
+    fn command(&self) -> u8 {
+        match self {
+            AllPixelsOnCommand::Normal => 0xa4,
+            AllPixelsOnCommand::AllOn => 0xa5,
+        }
+    }
+}
+
+/// Start read-modify-write mode
+///
+/// Column address is not incremented by display data reads and incremented by writes
+pub struct ReadModifyWriteCommand;
+
+impl Command for ReadModifyWriteCommand {
+    fn command(&self) -> u8 {
+        0xe0
+    }
+}
+
+/// End read-modify-write mode and restore the column address set before it was started
+pub struct EndReadModifyWriteCommand;
+
+impl Command for EndReadModifyWriteCommand {
+    fn command(&self) -> u8 {
+        0xee
+    }
+}
+
+/// No operation
+pub struct NopCommand;
+
+impl Command for NopCommand {
+    fn command(&self) -> u8 {
+        0xe3
+    }
+}
+
 /// Reset display
-pub(crate) struct ResetCommand;
+pub struct ResetCommand;
 
 impl Command for ResetCommand {
     fn command(&self) -> u8 {
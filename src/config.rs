@@ -0,0 +1,326 @@
+//! Display configuration
+
+use crate::command::*;
+
+/// LCD bias ratio
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bias {
+    /// 1/7 bias
+    Bias1_7,
+    /// 1/9 bias
+    Bias1_9,
+}
+
+impl From<Bias> for SetBiasCommand {
+    fn from(bias: Bias) -> Self {
+        match bias {
+            Bias::Bias1_7 => SetBiasCommand::Bias1_7,
+            Bias::Bias1_9 => SetBiasCommand::Bias1_9,
+        }
+    }
+}
+
+/// Scan direction of SEG outputs (horizontal direction)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SEGDirection {
+    /// SEG0 -> SEG131
+    Normal,
+    /// SEG131 -> SEG0
+    Reverse,
+}
+
+impl SEGDirection {
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            SEGDirection::Normal => SEGDirection::Reverse,
+            SEGDirection::Reverse => SEGDirection::Normal,
+        }
+    }
+}
+
+impl From<SEGDirection> for SetSEGDirectionCommand {
+    fn from(direction: SEGDirection) -> Self {
+        match direction {
+            SEGDirection::Normal => SetSEGDirectionCommand::Normal,
+            SEGDirection::Reverse => SetSEGDirectionCommand::Reverse,
+        }
+    }
+}
+
+/// Output direction of COM outputs (vertical direction)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum COMDirection {
+    /// COM0 -> COM63
+    Normal,
+    /// COM63 -> COM0
+    Reverse,
+}
+
+impl COMDirection {
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            COMDirection::Normal => COMDirection::Reverse,
+            COMDirection::Reverse => COMDirection::Normal,
+        }
+    }
+}
+
+impl From<COMDirection> for SetCOMDirectionCommand {
+    fn from(direction: COMDirection) -> Self {
+        match direction {
+            COMDirection::Normal => SetCOMDirectionCommand::Normal,
+            COMDirection::Reverse => SetCOMDirectionCommand::Reverse,
+        }
+    }
+}
+
+/// Display rotation, clockwise
+///
+/// 0° and 180° are done by the controller by changing SEG and COM directions,
+/// 90° and 270° additionally swap coordinates in software in buffered mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayRotation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl DisplayRotation {
+    /// Whether SEG and COM directions are reversed relative to configured ones
+    pub(crate) fn is_flipped(self) -> bool {
+        matches!(
+            self,
+            DisplayRotation::Rotate180 | DisplayRotation::Rotate270
+        )
+    }
+
+    /// Whether width and height are swapped
+    pub(crate) fn is_transposed(self) -> bool {
+        matches!(self, DisplayRotation::Rotate90 | DisplayRotation::Rotate270)
+    }
+
+    /// Map rotated coordinates to the display coordinates
+    pub(crate) fn map_point(self, x: u8, y: u8, display_width: u8) -> (u8, u8) {
+        if self.is_transposed() {
+            (display_width - 1 - y, x)
+        } else {
+            (x, y)
+        }
+    }
+}
+
+/// Regulation resistor ratio of the built-in voltage regulator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegulationRatio {
+    Ratio3_0,
+    Ratio3_5,
+    Ratio4_0,
+    Ratio4_5,
+    Ratio5_0,
+    Ratio5_5,
+    Ratio6_0,
+    Ratio6_5,
+}
+
+impl From<RegulationRatio> for SetRegulationResistorRatioCommand {
+    fn from(ratio: RegulationRatio) -> Self {
+        match ratio {
+            RegulationRatio::Ratio3_0 => SetRegulationResistorRatioCommand::Ratio3_0,
+            RegulationRatio::Ratio3_5 => SetRegulationResistorRatioCommand::Ratio3_5,
+            RegulationRatio::Ratio4_0 => SetRegulationResistorRatioCommand::Ratio4_0,
+            RegulationRatio::Ratio4_5 => SetRegulationResistorRatioCommand::Ratio4_5,
+            RegulationRatio::Ratio5_0 => SetRegulationResistorRatioCommand::Ratio5_0,
+            RegulationRatio::Ratio5_5 => SetRegulationResistorRatioCommand::Ratio5_5,
+            RegulationRatio::Ratio6_0 => SetRegulationResistorRatioCommand::Ratio6_0,
+            RegulationRatio::Ratio6_5 => SetRegulationResistorRatioCommand::Ratio6_5,
+        }
+    }
+}
+
+/// Voltage multiplier of the built-in booster
+///
+/// ST7567S supports only 4x and 5x levels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoosterRatio {
+    X4,
+    X5,
+}
+
+impl From<BoosterRatio> for SetBoosterRatioCommand {
+    fn from(ratio: BoosterRatio) -> Self {
+        match ratio {
+            BoosterRatio::X4 => SetBoosterRatioCommand::Ratio4x,
+            BoosterRatio::X5 => SetBoosterRatioCommand::Ratio5x,
+        }
+    }
+}
+
+/// State of the built-in power circuits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerControl {
+    /// Voltage booster (VB)
+    pub booster: bool,
+    /// Voltage regulator (VR)
+    pub regulator: bool,
+    /// Voltage follower (VF)
+    pub follower: bool,
+}
+
+impl PowerControl {
+    /// All power circuits are off
+    pub const OFF: Self = PowerControl {
+        booster: false,
+        regulator: false,
+        follower: false,
+    };
+
+    /// All power circuits are on
+    pub const ALL_ON: Self = PowerControl {
+        booster: true,
+        regulator: true,
+        follower: true,
+    };
+}
+
+impl From<PowerControl> for SetPowerControlCommand {
+    fn from(power_control: PowerControl) -> Self {
+        SetPowerControlCommand::new(
+            power_control.booster,
+            power_control.regulator,
+            power_control.follower,
+        )
+    }
+}
+
+/// Settings applied to the controller by [`init_with`]
+///
+/// Defaults match the settings used by [`init`]:
+/// bias 1/9, SEG normal, COM reverse, regulation ratio 5.0, electronic volume 40, start line 0,
+/// booster 4x, all power circuits on and no rotation
+///
+/// [`init`]: crate::display::ST7567S#method.init
+/// [`init_with`]: crate::display::ST7567S#method.init_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub(crate) bias: Bias,
+    pub(crate) seg_direction: SEGDirection,
+    pub(crate) com_direction: COMDirection,
+    pub(crate) regulation_ratio: RegulationRatio,
+    pub(crate) electronic_volume: u8,
+    pub(crate) start_line: u8,
+    pub(crate) booster_ratio: BoosterRatio,
+    pub(crate) power_control: PowerControl,
+    pub(crate) rotation: DisplayRotation,
+    /// Display inversion set at runtime, not changed by init
+    pub(crate) inverted: bool,
+    /// All pixels on mode set at runtime, not changed by init
+    pub(crate) all_pixels_on: bool,
+}
+
+impl DisplayConfig {
+    /// Create configuration with default settings
+    pub const fn new() -> Self {
+        DisplayConfig {
+            bias: Bias::Bias1_9,
+            seg_direction: SEGDirection::Normal,
+            com_direction: COMDirection::Reverse,
+            regulation_ratio: RegulationRatio::Ratio5_0,
+            electronic_volume: 40,
+            start_line: 0,
+            booster_ratio: BoosterRatio::X4,
+            power_control: PowerControl::ALL_ON,
+            rotation: DisplayRotation::Rotate0,
+            inverted: false,
+            all_pixels_on: false,
+        }
+    }
+
+    /// Set LCD bias ratio
+    pub const fn bias(mut self, bias: Bias) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Set scan direction of SEG outputs
+    pub const fn seg_direction(mut self, direction: SEGDirection) -> Self {
+        self.seg_direction = direction;
+        self
+    }
+
+    /// Set output direction of COM outputs
+    pub const fn com_direction(mut self, direction: COMDirection) -> Self {
+        self.com_direction = direction;
+        self
+    }
+
+    /// Set regulation resistor ratio
+    pub const fn regulation_ratio(mut self, ratio: RegulationRatio) -> Self {
+        self.regulation_ratio = ratio;
+        self
+    }
+
+    /// Set electronic volume (EV) level (0-63)
+    ///
+    /// Out of range values are reported by [`init_with`](crate::display::ST7567S#method.init_with)
+    pub const fn electronic_volume(mut self, level: u8) -> Self {
+        self.electronic_volume = level;
+        self
+    }
+
+    /// Set display start line (0-63)
+    ///
+    /// Out of range values are reported by [`init_with`](crate::display::ST7567S#method.init_with)
+    pub const fn start_line(mut self, line: u8) -> Self {
+        self.start_line = line;
+        self
+    }
+
+    /// Set booster level
+    ///
+    /// Panels running at lower VDD may need a higher level
+    pub const fn booster_ratio(mut self, ratio: BoosterRatio) -> Self {
+        self.booster_ratio = ratio;
+        self
+    }
+
+    /// Set power circuits to turn on
+    ///
+    /// Leave the booster off when the LCD voltage is supplied externally
+    pub const fn power_control(mut self, power_control: PowerControl) -> Self {
+        self.power_control = power_control;
+        self
+    }
+
+    /// Set display rotation
+    ///
+    /// SEG and COM directions are reversed for 180° and 270°
+    pub const fn rotation(mut self, rotation: DisplayRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// SEG direction sent to the controller with rotation applied
+    pub(crate) fn hw_seg_direction(&self) -> SEGDirection {
+        if self.rotation.is_flipped() {
+            self.seg_direction.reversed()
+        } else {
+            self.seg_direction
+        }
+    }
+
+    /// COM direction sent to the controller with rotation applied
+    pub(crate) fn hw_com_direction(&self) -> COMDirection {
+        if self.rotation.is_flipped() {
+            self.com_direction.reversed()
+        } else {
+            self.com_direction
+        }
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,10 @@
-pub(crate) const DISPLAY_WIDTH: u8 = 128;
-pub(crate) const DISPLAY_HEIGHT: u8 = 64;
-pub(crate) const BUFFER_SIZE: usize = (DISPLAY_WIDTH as usize) * (DISPLAY_HEIGHT as usize) / 8;
+pub(crate) const RAM_WIDTH: u8 = 132;
+pub(crate) const RAM_PAGES: u8 = 9;
+pub(crate) const RAM_LINES: u8 = 64;
+pub(crate) const ICON_PAGE: u8 = 8;
+/// Command bytes accepted by `display_interface_i2c::I2CInterface` in one transaction
+pub(crate) const MAX_COMMANDS_LEN: usize = 7;
+/// Reset "L" pulse width (tRW) in microseconds, the longest one over the VDD range
+pub(crate) const RESET_PULSE_WIDTH_US: u8 = 3;
+/// Reset time (tR) in microseconds, the longest one over the VDD range
+pub(crate) const RESET_TIME_US: u8 = 3;
@@ -1,13 +1,27 @@
 //! Display driver
 
-use crate::{command::*, consts::*};
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use crate::{
+    command::*,
+    config::{
+        COMDirection, DisplayConfig, DisplayRotation, PowerControl, RegulationRatio, SEGDirection,
+    },
+    consts::*,
+    interface::{ControllerInterface, ReadInterface},
+    size::{DisplaySize, DisplaySize128x64, NewZeroed, SizeCheck},
+};
+use core::marker::PhantomData;
+use display_interface::DisplayError;
+use embedded_hal::{
+    blocking::delay::{DelayMs, DelayUs},
+    digital::v2::OutputPin,
+};
 
 /// ST7565S display driver
 ///
-///  Works in two modes:
+///  Works in three modes:
 ///  - Direct Write Mode (by default): This mode allows you to write directly to the display memory by calling the [`draw`] method.
-///  - Buffered Mode: This mode allows you to modify an internal buffer by using methods like [`set_pixel`], [`clear`], or by using the [`embedded-graphics`] crate. Once you have made your changes, you can call the [`flush`] method to write the buffer to the display.
+///  - Buffered Mode: This mode allows you to modify an internal buffer by using methods like [`set_pixel`], [`clear`], or by using the [`embedded-graphics`] crate. Once you have made your changes, you can call the [`flush`] method to write the changed area of the buffer to the display.
+///  - Diff Buffered Mode: Same as Buffered Mode, but [`DiffBufferedMode`] keeps a copy of the last flushed buffer and sends only the bytes that differ from it, which also allows modifying the buffer directly.
 ///
 /// [`embedded-graphics`]: https://docs.rs/embedded-graphics
 /// [`set_pixel`]: crate::display::ST7567S#method.set_pixel
@@ -15,135 +29,893 @@ use display_interface::{DisplayError, WriteOnlyDataCommand};
 /// [`flush`]: crate::display::ST7567S#method.flush
 /// [`draw`]: crate::display::ST7567S#method.draw
 ///
-pub struct ST7567S<DI, MODE> {
+pub struct ST7567S<DI, MODE, SIZE = DisplaySize128x64> {
     pub(crate) mode: MODE,
     pub(crate) display_interface: DI,
+    pub(crate) config: DisplayConfig,
+    #[allow(dead_code)]
+    pub(crate) size: SIZE,
+}
+
+/// Size of the icon line bitmap used by [`ST7567S::set_icons`]
+pub const ICONS_BITMAP_SIZE: usize = (RAM_WIDTH as usize).div_ceil(8);
+
+/// Controller status read by [`ST7567S::read_status`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Status {
+    /// Controller is busy (D7)
+    ///
+    /// ST7567S does not report busy state and always reads it as `false`,
+    /// the flag is kept for controllers of the family that do
+    pub busy: bool,
+    /// SEG outputs are scanned in reverse direction (MX, D6)
+    pub seg_reversed: bool,
+    /// Display is on (inverted D, D5)
+    pub display_on: bool,
+    /// Hardware or software reset is in progress (RST, D4)
+    pub resetting: bool,
+}
+
+impl From<u8> for Status {
+    fn from(status: u8) -> Self {
+        Status {
+            busy: status & 0x80 != 0,
+            seg_reversed: status & 0x40 != 0,
+            display_on: status & 0x20 == 0,
+            resetting: status & 0x10 != 0,
+        }
+    }
 }
 
 /// Basic mode allowing only direct write to the screen controller memory
 pub struct DirectWriteMode;
 
+impl sealed::Mode for DirectWriteMode {}
+
+impl DisplayMode for DirectWriteMode {}
+
 /// Buffered mode allowing to collect changes and then flush them
-pub struct BufferedMode {
-    buffer: [u8; BUFFER_SIZE],
+///
+/// Tracks the bounding box of the pixels changed since the last flush, so only that area is sent.
+/// The buffer is stored inside the driver by default, or can be provided by the user as `BUF`,
+/// see [`into_buffered_graphics_mode_with_buffer`](ST7567S::into_buffered_graphics_mode_with_buffer)
+pub struct BufferedMode<SIZE: DisplaySize = DisplaySize128x64, BUF = <SIZE as DisplaySize>::Buffer>
+{
+    buffer: BUF,
+    /// Top left and bottom right corners of the changed area in the buffer
+    dirty_area: Option<((u8, u8), (u8, u8))>,
+    size: PhantomData<SIZE>,
 }
 
-impl BufferedMode {
+impl<SIZE: DisplaySize> BufferedMode<SIZE> {
     pub(crate) fn new() -> Self {
+        BufferedMode::with_buffer(SIZE::Buffer::new_zeroed())
+    }
+}
+
+impl<SIZE: DisplaySize, BUF> BufferedMode<SIZE, BUF> {
+    const FULL_AREA: ((u8, u8), (u8, u8)) = ((0, 0), (SIZE::WIDTH - 1, SIZE::HEIGHT - 1));
+
+    pub(crate) fn with_buffer(buffer: BUF) -> Self {
         BufferedMode {
-            buffer: [0; BUFFER_SIZE],
+            buffer,
+            dirty_area: Some(Self::FULL_AREA),
+            size: PhantomData,
+        }
+    }
+}
+
+impl<SIZE: DisplaySize, BUF: AsMut<[u8]>> sealed::PixelBuffer for BufferedMode<SIZE, BUF> {
+    fn buffer_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+
+    fn mark_dirty(&mut self, column: u8, line: u8) {
+        self.dirty_area = Some(match self.dirty_area {
+            Some((top_left, bottom_right)) => (
+                (top_left.0.min(column), top_left.1.min(line)),
+                (bottom_right.0.max(column), bottom_right.1.max(line)),
+            ),
+            None => ((column, line), (column, line)),
+        });
+    }
+}
+
+impl<SIZE: DisplaySize, BUF> sealed::Mode for BufferedMode<SIZE, BUF> {
+    fn mark_all_dirty(&mut self) {
+        self.dirty_area = Some(Self::FULL_AREA);
+    }
+}
+
+impl<SIZE: DisplaySize, BUF> DisplayMode for BufferedMode<SIZE, BUF> {}
+
+impl<SIZE: DisplaySize, BUF: AsMut<[u8]>> BufferedDisplayMode for BufferedMode<SIZE, BUF> {}
+
+/// Buffered mode keeping a copy of the last sent buffer
+///
+/// Compares the buffer with the copy on flush and sends only the changed bytes,
+/// no matter how the buffer was modified, at the cost of a second buffer
+pub struct DiffBufferedMode<SIZE: DisplaySize = DisplaySize128x64> {
+    buffer: SIZE::Buffer,
+    /// Buffer contents as they were sent to the display
+    shadow: SIZE::Buffer,
+    /// Whether the display RAM is known to match `shadow`
+    shadow_synced: bool,
+}
+
+impl<SIZE: DisplaySize> DiffBufferedMode<SIZE> {
+    pub(crate) fn new() -> Self {
+        DiffBufferedMode {
+            buffer: SIZE::Buffer::new_zeroed(),
+            shadow: SIZE::Buffer::new_zeroed(),
+            shadow_synced: false,
         }
     }
 }
 
-impl<DI: WriteOnlyDataCommand> ST7567S<DI, DirectWriteMode> {
-    /// Create new instance of ST7565S driver in DirectWriteMode
+impl<SIZE: DisplaySize> sealed::PixelBuffer for DiffBufferedMode<SIZE> {
+    fn buffer_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<SIZE: DisplaySize> sealed::Mode for DiffBufferedMode<SIZE> {
+    fn mark_all_dirty(&mut self) {
+        self.shadow_synced = false;
+    }
+}
+
+impl<SIZE: DisplaySize> DisplayMode for DiffBufferedMode<SIZE> {}
+
+impl<SIZE: DisplaySize> BufferedDisplayMode for DiffBufferedMode<SIZE> {}
+
+/// Driver modes: [`DirectWriteMode`], [`BufferedMode`] and [`DiffBufferedMode`]
+pub trait DisplayMode: sealed::Mode {}
+
+/// Modes keeping display content in an internal buffer: [`BufferedMode`] and [`DiffBufferedMode`]
+///
+/// The buffer is modified by [`set_pixel`](ST7567S::set_pixel), [`clear`](ST7567S::clear)
+/// or by using the `embedded-graphics` crate
+pub trait BufferedDisplayMode: DisplayMode + sealed::PixelBuffer {}
+
+mod sealed {
+    pub trait Mode {
+        /// Record that the whole display RAM may not match the buffer anymore,
+        /// e.g. after it was cleared by init or its addressing was changed
+        fn mark_all_dirty(&mut self) {}
+    }
+
+    pub trait PixelBuffer: Mode {
+        /// Internal buffer
+        fn buffer_mut(&mut self) -> &mut [u8];
+
+        /// Record a change of the pixel at given buffer column and line
+        fn mark_dirty(&mut self, _column: u8, _line: u8) {}
+    }
+}
+
+/// Delay provider used when no delays between init steps are required
+struct NoDelay;
+
+impl DelayMs<u8> for NoDelay {
+    fn delay_ms(&mut self, _ms: u8) {}
+}
+
+impl<DI: ControllerInterface> ST7567S<DI, DirectWriteMode> {
+    /// Create new instance of ST7565S driver in DirectWriteMode for 128x64 display
     ///
     /// # Arguments
     /// * `display_interface` - The interface abstraction from `display_interface` crate
     pub fn new(display_interface: DI) -> Self {
+        Self::with_size(display_interface, DisplaySize128x64)
+    }
+}
+
+impl<DI: ControllerInterface, SIZE: DisplaySize> ST7567S<DI, DirectWriteMode, SIZE> {
+    /// Create new instance of ST7565S driver in DirectWriteMode for display of given size
+    ///
+    /// # Arguments
+    /// * `display_interface` - The interface abstraction from `display_interface` crate
+    /// * `size` - Display size, e.g. [`DisplaySize132x65`](crate::size::DisplaySize132x65)
+    pub fn with_size(display_interface: DI, size: SIZE) -> Self {
+        let () = SizeCheck::<SIZE>::OK;
+
         ST7567S {
             mode: DirectWriteMode,
             display_interface,
+            config: DisplayConfig::new(),
+            size,
         }
     }
 
     /// Move driver to buffered mode
-    pub fn into_buffered_graphics_mode(self) -> ST7567S<DI, BufferedMode> {
+    pub fn into_buffered_graphics_mode(self) -> ST7567S<DI, BufferedMode<SIZE>, SIZE> {
         ST7567S {
             mode: BufferedMode::new(),
             display_interface: self.display_interface,
+            config: self.config,
+            size: self.size,
+        }
+    }
+
+    /// Move driver to buffered mode using provided buffer, e.g. `&'static mut [u8]` or a buffer placed in a specific memory region
+    ///
+    /// The buffer contents are kept and sent by the first [`flush`](ST7567S::flush),
+    /// see [`draw`](ST7567S::draw) for the layout.
+    /// Returns [`DisplayError::OutOfBoundsError`] if the buffer length is not [`DisplaySize::BUFFER_SIZE`]
+    ///
+    /// ```rust
+    /// # use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+    /// # use st7567s::{display::ST7567S, size::{DisplaySize, DisplaySize128x64}};
+    /// # struct Interface;
+    /// # impl WriteOnlyDataCommand for Interface {
+    /// #     fn send_commands(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// #     fn send_data(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+    /// # }
+    /// let mut buffer = [0; DisplaySize128x64::BUFFER_SIZE];
+    ///
+    /// let mut display = ST7567S::new(Interface)
+    ///     .into_buffered_graphics_mode_with_buffer(&mut buffer[..])
+    ///     .unwrap();
+    /// display.init().unwrap();
+    /// display.set_pixel(0, 0, true).unwrap();
+    /// display.flush().unwrap();
+    /// # drop(display);
+    /// # assert_eq!(buffer[0], 1);
+    /// ```
+    pub fn into_buffered_graphics_mode_with_buffer<BUF>(
+        self,
+        mut buffer: BUF,
+    ) -> Result<ST7567S<DI, BufferedMode<SIZE, BUF>, SIZE>, DisplayError>
+    where
+        BUF: AsMut<[u8]> + AsRef<[u8]>,
+    {
+        if buffer.as_mut().len() != SIZE::BUFFER_SIZE {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        Ok(ST7567S {
+            mode: BufferedMode::with_buffer(buffer),
+            display_interface: self.display_interface,
+            config: self.config,
+            size: self.size,
+        })
+    }
+
+    /// Move driver to buffered mode sending only the bytes changed since the last flush
+    pub fn into_diff_buffered_graphics_mode(self) -> ST7567S<DI, DiffBufferedMode<SIZE>, SIZE> {
+        ST7567S {
+            mode: DiffBufferedMode::new(),
+            display_interface: self.display_interface,
+            config: self.config,
+            size: self.size,
         }
     }
 }
 
-impl<DI: WriteOnlyDataCommand> ST7567S<DI, BufferedMode> {
+impl<DI: ControllerInterface, MODE: BufferedDisplayMode, SIZE: DisplaySize>
+    ST7567S<DI, MODE, SIZE>
+{
     /// Clear internal buffer
     pub fn clear(&mut self) {
-        self.mode.buffer = [0; BUFFER_SIZE];
+        self.mode.buffer_mut().fill(0);
+        self.mode.mark_all_dirty();
     }
 
     /// Set pixel in internal buffer
     ///
-    /// Pixel coordinates starts from top left corner and goes to bottom right corner
+    /// Pixel coordinates starts from top left corner and goes to bottom right corner of the rotated display
     pub fn set_pixel(&mut self, x: u8, y: u8, value: bool) -> Result<(), DisplayError> {
-        if x >= DISPLAY_WIDTH || y >= DISPLAY_HEIGHT {
-            return Err(DisplayError::OutOfBoundsError);
+        let (column, page, page_bit) = self.pixel_address(x, y)?;
+
+        let buffer = self.mode.buffer_mut();
+        let byte_idx = page as usize * (SIZE::WIDTH as usize) + column as usize;
+        let byte = with_bit(buffer[byte_idx], page_bit, value);
+        if byte != buffer[byte_idx] {
+            buffer[byte_idx] = byte;
+            self.mode.mark_dirty(column, page * 8 + page_bit);
         }
 
-        let column: usize = x as usize;
-        let page: usize = (y / 8) as usize;
-        let page_bit = y % 8;
+        Ok(())
+    }
+}
 
-        let byte_idx = page * (DISPLAY_WIDTH as usize) + column;
-        let byte = self.mode.buffer[byte_idx];
-        let bit_value: u8 = value.into();
-        let byte = byte & !(1 << page_bit) | (bit_value << page_bit);
+impl<DI, SIZE, BUF> ST7567S<DI, BufferedMode<SIZE, BUF>, SIZE>
+where
+    DI: ControllerInterface,
+    SIZE: DisplaySize,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Send the area of internal buffer changed since the last flush to the display
+    pub fn flush(&mut self) -> Result<(), DisplayError> {
+        let Some((top_left, bottom_right)) = self.mode.dirty_area else {
+            return Ok(());
+        };
 
-        self.mode.buffer[byte_idx] = byte;
+        Self::flush_buffer_chunks(
+            &mut self.display_interface,
+            Self::ram_offset(&self.config),
+            self.mode.buffer.as_ref(),
+            top_left,
+            bottom_right,
+        )?;
+        self.mode.dirty_area = None;
 
         Ok(())
     }
 
-    /// Send internal buffer to the display
+    /// Send the whole internal buffer to the display
+    ///
+    /// Needed only when the display RAM was changed bypassing the driver, e.g. by [`send_raw_commands`](ST7567S::send_raw_commands).
+    /// Driver methods changing the display RAM, like [`init`](ST7567S::init) or [`draw`](ST7567S::draw), make the next flush send the whole buffer
+    pub fn flush_all(&mut self) -> Result<(), DisplayError> {
+        self.mode.dirty_area = Some(BufferedMode::<SIZE, BUF>::FULL_AREA);
+        self.flush()
+    }
+}
+
+impl<DI: ControllerInterface, SIZE: DisplaySize> ST7567S<DI, DiffBufferedMode<SIZE>, SIZE> {
+    /// Internal buffer, see [`draw`](ST7567S::draw) for the layout
+    pub fn buffer(&self) -> &[u8] {
+        self.mode.buffer.as_ref()
+    }
+
+    /// Mutable internal buffer, see [`draw`](ST7567S::draw) for the layout
+    ///
+    /// Changes are detected by the next [`flush`](ST7567S::flush)
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        self.mode.buffer.as_mut()
+    }
+
+    /// Send the bytes of internal buffer changed since the last flush to the display
+    ///
+    /// The first run of changed bytes in a page is preceded by page and column address commands,
+    /// the following ones only by column address commands.
+    /// Runs are merged when the unchanged bytes between them are not longer than the column address commands
+    /// together with the [transfer overhead](ControllerInterface::TRANSFER_OVERHEAD) of the interface.
+    ///
+    /// The first flush after creating the mode, or after the display RAM was changed by the driver, sends the whole buffer
     pub fn flush(&mut self) -> Result<(), DisplayError> {
-        Self::flush_buffer_chunks(
-            &mut self.display_interface,
-            self.mode.buffer.as_slice(),
-            (0, 0),
-            (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1),
-        )
+        let ram_offset = Self::ram_offset(&self.config);
+        let buffer = self.mode.buffer.as_ref();
+        let shadow = self.mode.shadow.as_mut();
+
+        if !self.mode.shadow_synced {
+            Self::flush_buffer_chunks(
+                &mut self.display_interface,
+                ram_offset,
+                buffer,
+                (0, 0),
+                (SIZE::WIDTH - 1, SIZE::HEIGHT - 1),
+            )?;
+            shadow.copy_from_slice(buffer);
+            self.mode.shadow_synced = true;
+
+            return Ok(());
+        }
+
+        let width = SIZE::WIDTH as usize;
+        for (page, (line, shadow_line)) in buffer
+            .chunks(width)
+            .zip(shadow.chunks_mut(width))
+            .enumerate()
+        {
+            let mut page_addressed = false;
+            let mut run_start = first_difference(line, shadow_line, 0);
+            while let Some(start) = run_start {
+                let mut end = start + 1;
+                run_start = None;
+                while let Some(next) = first_difference(line, shadow_line, end) {
+                    if next - end > COLUMN_COMMANDS_LEN + DI::TRANSFER_OVERHEAD {
+                        run_start = Some(next);
+                        break;
+                    }
+                    end = next + 1;
+                }
+
+                let ram_column = ram_offset.0 + start as u8;
+                let mut commands = CommandBuffer::<ADDRESS_COMMANDS_LEN>::new();
+                if page_addressed {
+                    push_column_commands(&mut commands, ram_column)?;
+                } else {
+                    push_address_commands(&mut commands, ram_offset.1 + page as u8, ram_column)?;
+                    page_addressed = true;
+                }
+                self.display_interface
+                    .write_commands_and_data(commands.as_bytes(), &line[start..end])?;
+                shadow_line[start..end].copy_from_slice(&line[start..end]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the whole internal buffer to the display
+    ///
+    /// Needed only when the display RAM was changed bypassing the driver, e.g. by [`send_raw_commands`](ST7567S::send_raw_commands).
+    /// Driver methods changing the display RAM, like [`init`](ST7567S::init) or [`draw`](ST7567S::draw), make the next flush send the whole buffer
+    pub fn flush_all(&mut self) -> Result<(), DisplayError> {
+        self.mode.shadow_synced = false;
+        self.flush()
+    }
+}
+
+impl<DI: ControllerInterface + ReadInterface, SIZE: DisplaySize>
+    ST7567S<DI, DirectWriteMode, SIZE>
+{
+    /// Set pixel directly in the display RAM using read-modify-write mode
+    ///
+    /// Available for interfaces able to read from the controller and does not need a buffer,
+    /// but takes a read and a write on the bus for every pixel.
+    /// Pixel coordinates starts from top left corner and goes to bottom right corner of the rotated display
+    pub fn set_pixel(&mut self, x: u8, y: u8, value: bool) -> Result<(), DisplayError> {
+        let (column, page, page_bit) = self.pixel_address(x, y)?;
+        let ram_offset = Self::ram_offset(&self.config);
+
+        let mut commands = CommandBuffer::<4>::new();
+        push_address_commands(&mut commands, ram_offset.1 + page, ram_offset.0 + column)?;
+        commands.push(&ReadModifyWriteCommand)?;
+        commands.write(&mut self.display_interface)?;
+
+        // Dummy read first, see `read_display_data`
+        let mut byte = [0; 2];
+        self.display_interface.read_data(&mut byte)?;
+        self.display_interface
+            .write_data(&[with_bit(byte[1], page_bit, value)])?;
+
+        EndReadModifyWriteCommand.write(&mut self.display_interface)
     }
 }
 
-impl<DI: WriteOnlyDataCommand, MODE> ST7567S<DI, MODE> {
-    /// Send init commands to the display and turn it on
+impl<DI: ControllerInterface, MODE: DisplayMode, SIZE: DisplaySize> ST7567S<DI, MODE, SIZE> {
+    /// Send init commands with default settings to the display and turn it on
+    ///
+    /// Power circuits are turned on without delays between steps, use [`init_with_delay`](ST7567S::init_with_delay)
+    /// if the panel needs time for the voltages to settle
     pub fn init(&mut self) -> Result<(), DisplayError> {
-        SetBiasCommand::Bias1_9.write(&mut self.display_interface)?;
-        SetSEGDirectionCommand::Normal.write(&mut self.display_interface)?;
-        SetCOMDirectionCommand::Reverse.write(&mut self.display_interface)?;
-        SetRegulationResistorRatioCommand::Ratio5_0.write(&mut self.display_interface)?;
-        SetElectronicVolumeCommand::new(40)
-            .unwrap()
-            .write(&mut self.display_interface)?;
-        SetPowerControlCommand::BoosterOn.write(&mut self.display_interface)?;
-        SetPowerControlCommand::VoltageRegulatorOn.write(&mut self.display_interface)?;
-        SetPowerControlCommand::VoltageFollowerOn.write(&mut self.display_interface)?;
-        SetStartLineCommand::new(0)
-            .unwrap()
+        self.init_with(DisplayConfig::new())
+    }
+
+    /// Send init commands with provided settings to the display and turn it on
+    ///
+    /// Power circuits are turned on without delays between steps, see [`init`](ST7567S::init)
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the electronic volume or the start line is out of range,
+    /// or if the COM direction doesn't suit a display using the icon line as the last line,
+    /// see [`DisplaySize132x65`](crate::size::DisplaySize132x65)
+    pub fn init_with(&mut self, config: DisplayConfig) -> Result<(), DisplayError> {
+        self.init_with_delay(config, &mut NoDelay, 0)
+    }
+
+    /// Send init commands with provided settings to the display and turn it on,
+    /// waiting `step_delay_ms` after each power circuit is turned on
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the electronic volume or the start line is out of range,
+    /// or if the COM direction doesn't suit a display using the icon line as the last line,
+    /// see [`DisplaySize132x65`](crate::size::DisplaySize132x65)
+    pub fn init_with_delay<D: DelayMs<u8>>(
+        &mut self,
+        config: DisplayConfig,
+        delay: &mut D,
+        step_delay_ms: u8,
+    ) -> Result<(), DisplayError> {
+        Self::check_com_direction(&config)?;
+        let electronic_volume = SetElectronicVolumeCommand::new(config.electronic_volume)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+        let start_line =
+            SetStartLineCommand::new(config.start_line).ok_or(DisplayError::OutOfBoundsError)?;
+
+        let mut commands = CommandBuffer::<MAX_COMMANDS_LEN>::new();
+        commands.push(&SetBiasCommand::from(config.bias))?;
+        commands.push(&SetSEGDirectionCommand::from(config.hw_seg_direction()))?;
+        commands.push(&SetCOMDirectionCommand::from(config.hw_com_direction()))?;
+        commands.push(&SetRegulationResistorRatioCommand::from(
+            config.regulation_ratio,
+        ))?;
+        commands.push(&start_line)?;
+        commands.write(&mut self.display_interface)?;
+
+        let mut commands = CommandBuffer::<MAX_COMMANDS_LEN>::new();
+        commands.push(&electronic_volume)?;
+        commands.push(&SetBoosterRatioCommand::from(config.booster_ratio))?;
+        commands.write(&mut self.display_interface)?;
+
+        self.power_up(config.power_control, step_delay_ms, delay)?;
+
+        self.config = DisplayConfig {
+            inverted: self.config.inverted,
+            all_pixels_on: self.config.all_pixels_on,
+            ..config
+        };
+
+        self.mode.mark_all_dirty();
+        self.clear_ram()?;
+
+        DisplayOnCommand::On.write(&mut self.display_interface)?;
+
+        Ok(())
+    }
+
+    /// Reset the controller with the RST pin and send init commands with provided settings,
+    /// see [`reset_hw`](ST7567S::reset_hw) and [`init_with_delay`](ST7567S::init_with_delay)
+    pub fn init_with_reset<RST, D>(
+        &mut self,
+        config: DisplayConfig,
+        rst: &mut RST,
+        delay: &mut D,
+        step_delay_ms: u8,
+    ) -> Result<(), DisplayError>
+    where
+        RST: OutputPin,
+        D: DelayUs<u8> + DelayMs<u8>,
+    {
+        self.reset_hw(rst, delay)?;
+        self.init_with_delay(config, delay, step_delay_ms)
+    }
+
+    /// Fill the whole controller RAM with zeros, including columns and lines outside of the display
+    fn clear_ram(&mut self) -> Result<(), DisplayError> {
+        (0..RAM_PAGES).try_for_each(|page| {
+            self.display_interface.write_commands_and_data(
+                address_commands(page, 0)?.as_bytes(),
+                &[0; RAM_WIDTH as usize],
+            )
+        })
+    }
+
+    /// Turn on requested power circuits one by one: booster, regulator, follower
+    fn power_up<D: DelayMs<u8>>(
+        &mut self,
+        power_control: PowerControl,
+        step_delay_ms: u8,
+        delay: &mut D,
+    ) -> Result<(), DisplayError> {
+        let steps = [
+            PowerControl {
+                booster: power_control.booster,
+                ..PowerControl::OFF
+            },
+            PowerControl {
+                booster: power_control.booster,
+                regulator: power_control.regulator,
+                ..PowerControl::OFF
+            },
+            power_control,
+        ];
+
+        let mut current = PowerControl::OFF;
+        for step in steps {
+            if step == current {
+                continue;
+            }
+            SetPowerControlCommand::from(step).write(&mut self.display_interface)?;
+            delay.delay_ms(step_delay_ms);
+            current = step;
+        }
+
+        Ok(())
+    }
+
+    /// Set contrast by changing electronic volume (EV) level (0-63)
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the level is out of range
+    pub fn set_contrast(&mut self, level: u8) -> Result<(), DisplayError> {
+        SetElectronicVolumeCommand::new(level)
+            .ok_or(DisplayError::OutOfBoundsError)?
             .write(&mut self.display_interface)?;
+        self.config.electronic_volume = level;
 
-        self.draw([0; BUFFER_SIZE].as_slice())?;
+        Ok(())
+    }
+
+    /// Set regulation resistor ratio of the built-in voltage regulator
+    ///
+    /// Together with the electronic volume it defines the LCD operating voltage
+    pub fn set_regulation_ratio(&mut self, ratio: RegulationRatio) -> Result<(), DisplayError> {
+        SetRegulationResistorRatioCommand::from(ratio).write(&mut self.display_interface)?;
+        self.config.regulation_ratio = ratio;
 
+        Ok(())
+    }
+
+    /// Invert all pixels on the display without changing the display memory
+    pub fn set_inverted(&mut self, inverted: bool) -> Result<(), DisplayError> {
+        if inverted {
+            InverseDisplayCommand::Inverse.write(&mut self.display_interface)?;
+        } else {
+            InverseDisplayCommand::Normal.write(&mut self.display_interface)?;
+        }
+        self.config.inverted = inverted;
+
+        Ok(())
+    }
+
+    /// Turn on all pixels on the display without changing the display memory
+    ///
+    /// Display memory content is shown again when the mode is turned off
+    pub fn set_all_pixels_on(&mut self, all_on: bool) -> Result<(), DisplayError> {
+        if all_on {
+            AllPixelsOnCommand::AllOn.write(&mut self.display_interface)?;
+        } else {
+            AllPixelsOnCommand::Normal.write(&mut self.display_interface)?;
+        }
+        self.config.all_pixels_on = all_on;
+
+        Ok(())
+    }
+
+    /// Enter power save mode: display off + all pixels on
+    ///
+    /// Built-in power circuits and the oscillator are stopped,
+    /// display memory and register settings are kept
+    pub fn sleep(&mut self) -> Result<(), DisplayError> {
+        DisplayOnCommand::Off.write(&mut self.display_interface)?;
+        AllPixelsOnCommand::AllOn.write(&mut self.display_interface)
+    }
+
+    /// Exit power save mode: all pixels off + display on
+    ///
+    /// Settings are restored to the state before [`sleep`](ST7567S::sleep),
+    /// including the all pixels on mode set by [`set_all_pixels_on`](ST7567S::set_all_pixels_on)
+    pub fn wake(&mut self) -> Result<(), DisplayError> {
+        AllPixelsOnCommand::Normal.write(&mut self.display_interface)?;
         DisplayOnCommand::On.write(&mut self.display_interface)?;
+        if self.config.all_pixels_on {
+            AllPixelsOnCommand::AllOn.write(&mut self.display_interface)?;
+        }
+
+        Ok(())
+    }
+
+    /// Turn the display off and stop built-in power circuits
+    ///
+    /// Power circuits need up to 250ms to discharge before VDD can be turned off.
+    /// The display interface can be taken back with [`release`](ST7567S::release), also after an error
+    pub fn shutdown(&mut self) -> Result<(), DisplayError> {
+        DisplayOnCommand::Off.write(&mut self.display_interface)?;
+        SetPowerControlCommand::from(PowerControl::OFF).write(&mut self.display_interface)?;
+        AllPixelsOnCommand::AllOn.write(&mut self.display_interface)
+    }
+
+    /// Release the display interface
+    pub fn release(self) -> DI {
+        self.display_interface
+    }
+
+    /// Set RAM line (0-63) displayed at the top row of the display
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the line is out of range
+    pub fn set_start_line(&mut self, line: u8) -> Result<(), DisplayError> {
+        SetStartLineCommand::new(line)
+            .ok_or(DisplayError::OutOfBoundsError)?
+            .write(&mut self.display_interface)?;
+        self.config.start_line = line;
+
+        Ok(())
+    }
+
+    /// Scroll display content vertically by moving the start line by `lines`,
+    /// wrapping around between RAM lines 63 and 0
+    ///
+    /// Positive values move the content up, negative values move it down
+    pub fn scroll(&mut self, lines: i8) -> Result<(), DisplayError> {
+        let line = (self.config.start_line as i16 + lines as i16).rem_euclid(RAM_LINES as i16);
+        self.set_start_line(line as u8)
+    }
+
+    /// Set display rotation
+    ///
+    /// 0° and 180° are applied by the controller, 90° and 270° also swap coordinates used by buffered mode.
+    /// Display content should be redrawn after changing rotation, the next flush in buffered modes sends the whole buffer.
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the rotation reverses COM direction of a display
+    /// using the icon line as the last line, see [`DisplaySize132x65`](crate::size::DisplaySize132x65)
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), DisplayError> {
+        let config = self.config.rotation(rotation);
+        Self::check_com_direction(&config)?;
+
+        let mut commands = CommandBuffer::<2>::new();
+        commands.push(&SetSEGDirectionCommand::from(config.hw_seg_direction()))?;
+        commands.push(&SetCOMDirectionCommand::from(config.hw_com_direction()))?;
+        commands.write(&mut self.display_interface)?;
+        self.config = config;
+        self.mode.mark_all_dirty();
+
+        Ok(())
+    }
+
+    /// Get display rotation
+    pub fn rotation(&self) -> DisplayRotation {
+        self.config.rotation
+    }
+
+    /// Get width and height of the drawing area with rotation applied
+    pub fn dimensions(&self) -> (u8, u8) {
+        if self.config.rotation.is_transposed() {
+            (SIZE::HEIGHT, SIZE::WIDTH)
+        } else {
+            (SIZE::WIDTH, SIZE::HEIGHT)
+        }
+    }
+
+    /// Column, page and bit in the page of the pixel at given rotated display coordinates
+    fn pixel_address(&self, x: u8, y: u8) -> Result<(u8, u8, u8), DisplayError> {
+        let (width, height) = self.dimensions();
+        if x >= width || y >= height {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        let (x, y) = self.config.rotation.map_point(x, y, SIZE::WIDTH);
+
+        Ok((x, y / 8, y % 8))
+    }
+
+    /// Check that the icon line is shown right below the last RAM line for displays using it as the last line,
+    /// which is the case only for normal COM direction
+    fn check_com_direction(config: &DisplayConfig) -> Result<(), DisplayError> {
+        if SIZE::HEIGHT > RAM_LINES && config.hw_com_direction() == COMDirection::Reverse {
+            return Err(DisplayError::OutOfBoundsError);
+        }
 
         Ok(())
     }
 
+    /// Column and page of the controller RAM shown at the top left corner of the display
+    ///
+    /// The display is wired to SEG outputs starting from [`DisplaySize::COLUMN_OFFSET`]
+    /// and to COM outputs starting from COM0, so with reversed directions
+    /// the visible area moves to the end of the RAM
+    pub(crate) fn ram_offset(config: &DisplayConfig) -> (u8, u8) {
+        let column_offset = match config.hw_seg_direction() {
+            SEGDirection::Normal => SIZE::COLUMN_OFFSET,
+            SEGDirection::Reverse => RAM_WIDTH - SIZE::WIDTH - SIZE::COLUMN_OFFSET,
+        };
+        let page_offset = match config.hw_com_direction() {
+            COMDirection::Normal => 0,
+            COMDirection::Reverse => RAM_LINES.saturating_sub(SIZE::HEIGHT) / 8,
+        };
+
+        (column_offset, page_offset)
+    }
+
+    /// Set state of all icons on the icon line (COM64)
+    ///
+    /// `icons` is a bitmap where bit `n % 8` of byte `n / 8` controls the icon connected to SEG`n` output.
+    /// Icons should be set again after changing rotation.
+    /// For displays using the icon line as the last line (e.g. 132x65) it is overwritten by the next flush
+    pub fn set_icons(&mut self, icons: &[u8; ICONS_BITMAP_SIZE]) -> Result<(), DisplayError> {
+        let mut line = [0; RAM_WIDTH as usize];
+        for segment in 0..RAM_WIDTH {
+            let column = Self::icon_column(&self.config, segment) as usize;
+            let segment = segment as usize;
+            line[column] = (icons[segment / 8] >> (segment % 8)) & 1;
+        }
+
+        self.write_icon_line(0, &line)
+    }
+
+    /// Set state of the icon connected to SEG`segment` output (0-131)
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the segment is out of range
+    pub fn set_icon(&mut self, segment: u8, on: bool) -> Result<(), DisplayError> {
+        if segment >= RAM_WIDTH {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        let column = Self::icon_column(&self.config, segment);
+        self.write_icon_line(column, &[on.into()])
+    }
+
+    /// Turn off all icons on the icon line
+    pub fn clear_icons(&mut self) -> Result<(), DisplayError> {
+        self.write_icon_line(0, &[0; RAM_WIDTH as usize])
+    }
+
+    /// RAM column connected to SEG`segment` output
+    fn icon_column(config: &DisplayConfig, segment: u8) -> u8 {
+        match config.hw_seg_direction() {
+            SEGDirection::Normal => segment,
+            SEGDirection::Reverse => RAM_WIDTH - 1 - segment,
+        }
+    }
+
+    fn write_icon_line(&mut self, column: u8, data: &[u8]) -> Result<(), DisplayError> {
+        // The icon line is a part of the buffer for displays using it as the last line
+        if SIZE::HEIGHT > RAM_LINES {
+            self.mode.mark_all_dirty();
+        }
+        self.display_interface
+            .write_commands_and_data(address_commands(ICON_PAGE, column)?.as_bytes(), data)
+    }
+
+    /// Send typed command to the display
+    ///
+    /// Changes made this way are not tracked by the driver
+    pub fn send_command(&mut self, command: &impl Command) -> Result<(), DisplayError> {
+        command.write(&mut self.display_interface)
+    }
+
+    /// Send raw command bytes to the display
+    ///
+    /// Changes made this way are not tracked by the driver.
+    /// Long sequences are split into transactions like the ones of [`CommandBuffer`]
+    pub fn send_raw_commands(&mut self, commands: &[u8]) -> Result<(), DisplayError> {
+        write_command_chunks(
+            &mut self.display_interface,
+            commands,
+            raw_command_lens(commands),
+        )
+    }
+
     /// Reset some display parameters to default values: Start Line, Column Address, Page Address and COM Direction.
     /// Usually doesn't need to be called
     pub fn reset(&mut self) -> Result<(), DisplayError> {
-        ResetCommand.write(&mut self.display_interface)
+        ResetCommand.write(&mut self.display_interface)?;
+        self.config.start_line = 0;
+        self.config.com_direction = if self.config.rotation.is_flipped() {
+            COMDirection::Reverse
+        } else {
+            COMDirection::Normal
+        };
+        self.mode.mark_all_dirty();
+
+        Ok(())
+    }
+
+    /// Reset the controller with the RST pin
+    ///
+    /// Holds RST low for the reset pulse width and waits for the reset time from the datasheet.
+    /// Unlike [`reset`](ST7567S::reset), turns the display and the power circuits off and resets all settings,
+    /// so the display has to be initialized again, e.g. with [`init_with_reset`](ST7567S::init_with_reset).
+    /// Returns [`DisplayError::RSError`] if the pin can't be set
+    pub fn reset_hw<RST: OutputPin, D: DelayUs<u8>>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut D,
+    ) -> Result<(), DisplayError> {
+        rst.set_low().map_err(|_| DisplayError::RSError)?;
+        delay.delay_us(RESET_PULSE_WIDTH_US);
+        rst.set_high().map_err(|_| DisplayError::RSError)?;
+        delay.delay_us(RESET_TIME_US);
+        self.mode.mark_all_dirty();
+
+        let (seg_direction, com_direction) = if self.config.rotation.is_flipped() {
+            (SEGDirection::Reverse, COMDirection::Reverse)
+        } else {
+            (SEGDirection::Normal, COMDirection::Normal)
+        };
+        self.config = DisplayConfig::new()
+            .seg_direction(seg_direction)
+            .com_direction(com_direction)
+            .electronic_volume(32)
+            .power_control(PowerControl::OFF)
+            .rotation(self.config.rotation);
+
+        Ok(())
     }
 
     /// Send buffer to the display
     ///
-    /// Buffer represents by 8 pages of 128 columns where 1 byte represents 8 vertical pixels
+    /// Buffer represents by pages of 8 lines (8 pages for 64 lines display) of display width columns
+    /// where 1 byte represents 8 vertical pixels
     pub fn draw(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
-        if buffer.len() != BUFFER_SIZE {
+        if buffer.len() != SIZE::BUFFER_SIZE {
             return Err(DisplayError::OutOfBoundsError);
         }
 
+        self.mode.mark_all_dirty();
         Self::flush_buffer_chunks(
             &mut self.display_interface,
+            Self::ram_offset(&self.config),
             buffer,
             (0, 0),
-            (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1),
+            (SIZE::WIDTH - 1, SIZE::HEIGHT - 1),
         )
     }
 
     /// Send part of the buffer to the display.
-    /// Buffer represents by 8 pages of 128 columns where 1 byte represents 8 vertical pixels
+    /// Buffer represents by pages of 8 lines (8 pages for 64 lines display) of display width columns
+    /// where 1 byte represents 8 vertical pixels
     ///
     /// # Arguments
     /// * `buffer` - the entire buffer from which the required part will be sent
@@ -154,19 +926,27 @@ impl<DI: WriteOnlyDataCommand, MODE> ST7567S<DI, MODE> {
         top_left: (u8, u8),
         bottom_right: (u8, u8),
     ) -> Result<(), DisplayError> {
-        Self::flush_buffer_chunks(&mut self.display_interface, buffer, top_left, bottom_right)
+        self.mode.mark_all_dirty();
+        Self::flush_buffer_chunks(
+            &mut self.display_interface,
+            Self::ram_offset(&self.config),
+            buffer,
+            top_left,
+            bottom_right,
+        )
     }
 
     pub(crate) fn flush_buffer_chunks(
         display_interface: &mut DI,
+        ram_offset: (u8, u8),
         buffer: &[u8],
         top_left: (u8, u8),
         bottom_right: (u8, u8),
     ) -> Result<(), DisplayError> {
-        if top_left.0 >= DISPLAY_WIDTH || top_left.1 >= DISPLAY_HEIGHT {
+        if top_left.0 >= SIZE::WIDTH || top_left.1 >= SIZE::HEIGHT {
             return Err(DisplayError::OutOfBoundsError);
         }
-        if bottom_right.0 >= DISPLAY_WIDTH || bottom_right.1 >= DISPLAY_HEIGHT {
+        if bottom_right.0 >= SIZE::WIDTH || bottom_right.1 >= SIZE::HEIGHT {
             return Err(DisplayError::OutOfBoundsError);
         }
         if top_left.0 > bottom_right.0 || top_left.1 > bottom_right.1 {
@@ -174,29 +954,98 @@ impl<DI: WriteOnlyDataCommand, MODE> ST7567S<DI, MODE> {
         }
 
         let first_page: usize = (top_left.1 / 8) as usize;
+        let first_ram_column: u8 = top_left.0 + ram_offset.0;
         let first_column: usize = top_left.0 as usize;
 
         let last_page: usize = (bottom_right.1 / 8) as usize;
         let last_column: usize = bottom_right.0 as usize;
 
         buffer
-            .chunks(DISPLAY_WIDTH as usize)
+            .chunks(SIZE::WIDTH as usize)
             .skip(first_page)
             .take(last_page - first_page + 1)
             .map(|page| &page[first_column..=last_column])
             .enumerate()
             .try_for_each(|(page_idx, page)| {
-                SetPageAddressCommand::new(first_page as u8 + page_idx as u8)
-                    .unwrap()
-                    .write(display_interface)?;
-                SetColumnAddressLSNibbleCommand::new(first_column as u8)
-                    .unwrap()
-                    .write(display_interface)?;
-                SetColumnAddressMSNibbleCommand::new(first_column as u8)
-                    .unwrap()
-                    .write(display_interface)?;
-
-                display_interface.send_data(display_interface::DataFormat::U8(page))
+                let commands = address_commands(
+                    ram_offset.1 + first_page as u8 + page_idx as u8,
+                    first_ram_column,
+                )?;
+                display_interface.write_commands_and_data(commands.as_bytes(), page)
             })
     }
 }
+
+impl<DI: ControllerInterface + ReadInterface, MODE, SIZE: DisplaySize> ST7567S<DI, MODE, SIZE> {
+    /// Read controller status
+    pub fn read_status(&mut self) -> Result<Status, DisplayError> {
+        self.display_interface.read_status().map(Status::from)
+    }
+
+    /// Read display data RAM starting from given RAM page (0-8) and column (0-131)
+    ///
+    /// Unlike [`draw`](ST7567S::draw), addresses are not adjusted for display size, rotation and directions.
+    /// The column address is incremented after every byte read
+    pub fn read_display_data(
+        &mut self,
+        page: u8,
+        column: u8,
+        data: &mut [u8],
+    ) -> Result<(), DisplayError> {
+        self.display_interface
+            .write_commands(address_commands(page, column)?.as_bytes())?;
+
+        // The first read after setting the address returns the bus latch contents
+        let mut dummy = [0];
+        self.display_interface.read_data(&mut dummy)?;
+        self.display_interface.read_data(data)
+    }
+}
+
+/// Length of commands moving RAM pointer, see [`address_commands`]
+const ADDRESS_COMMANDS_LEN: usize = 3;
+/// Length of commands moving RAM pointer within the current page, see [`push_column_commands`]
+const COLUMN_COMMANDS_LEN: usize = 2;
+
+/// Index of the first byte starting from `from` which differs in `a` and `b`
+fn first_difference(a: &[u8], b: &[u8], from: usize) -> Option<usize> {
+    (from..a.len()).find(|&idx| a[idx] != b[idx])
+}
+
+/// `byte` with bit `bit` set to `value`
+fn with_bit(byte: u8, bit: u8, value: bool) -> u8 {
+    byte & !(1 << bit) | (u8::from(value) << bit)
+}
+
+/// Commands moving RAM pointer to given page and column
+fn address_commands(
+    page: u8,
+    column: u8,
+) -> Result<CommandBuffer<ADDRESS_COMMANDS_LEN>, DisplayError> {
+    let mut commands = CommandBuffer::new();
+    push_address_commands(&mut commands, page, column)?;
+
+    Ok(commands)
+}
+
+/// Append commands moving RAM pointer to given page and column
+fn push_address_commands<const N: usize>(
+    commands: &mut CommandBuffer<N>,
+    page: u8,
+    column: u8,
+) -> Result<(), DisplayError> {
+    commands.push(&SetPageAddressCommand::new(page).ok_or(DisplayError::OutOfBoundsError)?)?;
+    push_column_commands(commands, column)
+}
+
+/// Append commands moving RAM pointer to given column of the current page
+fn push_column_commands<const N: usize>(
+    commands: &mut CommandBuffer<N>,
+    column: u8,
+) -> Result<(), DisplayError> {
+    commands.push(
+        &SetColumnAddressLSNibbleCommand::new(column).ok_or(DisplayError::OutOfBoundsError)?,
+    )?;
+    commands
+        .push(&SetColumnAddressMSNibbleCommand::new(column).ok_or(DisplayError::OutOfBoundsError)?)
+}
@@ -1,10 +1,11 @@
 //! [`embedded-graphics`](https://docs.rs/embedded-graphics) support
 
 use crate::{
-    consts::*,
-    display::{BufferedMode, ST7567S},
+    display::{BufferedDisplayMode, ST7567S},
+    interface::ControllerInterface,
+    size::DisplaySize,
 };
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use display_interface::DisplayError;
 use embedded_graphics_core::{
     draw_target::DrawTarget,
     pixelcolor::BinaryColor,
@@ -12,13 +13,18 @@ use embedded_graphics_core::{
     Pixel,
 };
 
-impl<DI: WriteOnlyDataCommand> OriginDimensions for ST7567S<DI, BufferedMode> {
+impl<DI: ControllerInterface, MODE: BufferedDisplayMode, SIZE: DisplaySize> OriginDimensions
+    for ST7567S<DI, MODE, SIZE>
+{
     fn size(&self) -> Size {
-        Size::new(DISPLAY_WIDTH.into(), DISPLAY_HEIGHT.into())
+        let (width, height) = self.dimensions();
+        Size::new(width.into(), height.into())
     }
 }
 
-impl<DI: WriteOnlyDataCommand> DrawTarget for ST7567S<DI, BufferedMode> {
+impl<DI: ControllerInterface, MODE: BufferedDisplayMode, SIZE: DisplaySize> DrawTarget
+    for ST7567S<DI, MODE, SIZE>
+{
     type Color = BinaryColor;
 
     type Error = DisplayError;
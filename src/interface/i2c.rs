@@ -0,0 +1,150 @@
+pub use display_interface_i2c::I2CInterface;
+
+use super::ControllerInterface;
+use crate::{command::*, consts::RAM_WIDTH};
+use display_interface::DisplayError;
+use embedded_hal::blocking::i2c::Write;
+
+/// Default I2C address of the ST7567S
+///
+/// Controller reserves addresses 0x3c-0x3f, the low bits are selected by SA0 and SA1 pins
+pub const DEFAULT_I2C_ADDRESS: u8 = 0x3f;
+
+/// Control byte: last control byte, command bytes follow
+const CONTROL_COMMANDS: u8 = 0x00;
+/// Control byte: last control byte, display data bytes follow
+const CONTROL_DATA: u8 = 0x40;
+/// Control byte: one command byte and another control byte follow
+const CONTROL_CONTINUED_COMMAND: u8 = 0x80;
+
+/// Maximum number of commands packed together with display data
+const MAX_PACKED_COMMANDS: usize = 8;
+/// Transfer buffer fits packed commands and a whole RAM page
+const TRANSFER_BUFFER_SIZE: usize = 2 * MAX_PACKED_COMMANDS + 1 + RAM_WIDTH as usize;
+
+/// Wrapper for creating an I2CInterface with device-specific parameters
+pub struct I2CDisplayInterface;
+
+impl I2CDisplayInterface {
+    /// Create a new I2CInterface for the ST7567S display
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<I2C>(i2c: I2C) -> I2CInterface<I2C>
+    where
+        I2C: Write,
+    {
+        Self::new_with_address(i2c, DEFAULT_I2C_ADDRESS)
+    }
+
+    /// Create a new I2CInterface for the ST7567S display with custom address
+    pub fn new_with_address<I2C>(i2c: I2C, address: u8) -> I2CInterface<I2C>
+    where
+        I2C: Write,
+    {
+        I2CInterface::new(i2c, address, CONTROL_DATA)
+    }
+
+    /// Check if the display acknowledges the address by sending a NOP command
+    pub fn probe<I2C>(i2c: &mut I2C, address: u8) -> Result<(), DisplayNotPresent<I2C::Error>>
+    where
+        I2C: Write,
+    {
+        i2c.write(address, &[CONTROL_COMMANDS, NopCommand.command()])
+            .map_err(DisplayNotPresent)
+    }
+}
+
+/// Error returned by [`I2CDisplayInterface::probe`] when the display doesn't respond, contains the bus error
+#[derive(Debug)]
+pub struct DisplayNotPresent<E>(pub E);
+
+/// Native ST7567S I2C interface
+///
+/// Uses control bytes with the continuation (Co) bit to send
+/// page and column address commands together with display data in a single transaction
+pub struct NativeI2CInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C: Write> NativeI2CInterface<I2C> {
+    /// Create a new interface with the default address
+    pub fn new(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, DEFAULT_I2C_ADDRESS)
+    }
+
+    /// Create a new interface with custom address
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        NativeI2CInterface { i2c, address }
+    }
+
+    /// Release the I2C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Send control byte followed by bytes, split into multiple transactions if needed
+    fn write_stream(&mut self, control: u8, bytes: &[u8]) -> Result<(), DisplayError> {
+        let mut buffer = [0; TRANSFER_BUFFER_SIZE];
+        buffer[0] = control;
+
+        bytes
+            .chunks(TRANSFER_BUFFER_SIZE - 1)
+            .try_for_each(|chunk| {
+                buffer[1..=chunk.len()].copy_from_slice(chunk);
+                self.i2c
+                    .write(self.address, &buffer[..=chunk.len()])
+                    .map_err(|_| DisplayError::BusWriteError)
+            })
+    }
+}
+
+impl<I2C: Write> ControllerInterface for NativeI2CInterface<I2C> {
+    /// Address byte, control bytes of two packed commands and the data control byte
+    const TRANSFER_OVERHEAD: usize = 4;
+
+    fn write_commands(&mut self, commands: &[u8]) -> Result<(), DisplayError> {
+        self.write_stream(CONTROL_COMMANDS, commands)
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), DisplayError> {
+        self.write_stream(CONTROL_DATA, data)
+    }
+
+    fn write_commands_and_data(
+        &mut self,
+        commands: &[u8],
+        data: &[u8],
+    ) -> Result<(), DisplayError> {
+        if data.is_empty() {
+            return self.write_commands(commands);
+        }
+        if commands.len() > MAX_PACKED_COMMANDS {
+            self.write_commands(commands)?;
+            return self.write_data(data);
+        }
+
+        let mut buffer = [0; TRANSFER_BUFFER_SIZE];
+        let mut len = 0;
+        for &command in commands {
+            buffer[len] = CONTROL_CONTINUED_COMMAND;
+            buffer[len + 1] = command;
+            len += 2;
+        }
+        buffer[len] = CONTROL_DATA;
+        len += 1;
+
+        let (packed, rest) = data.split_at(data.len().min(TRANSFER_BUFFER_SIZE - len));
+        buffer[len..len + packed.len()].copy_from_slice(packed);
+        len += packed.len();
+
+        self.i2c
+            .write(self.address, &buffer[..len])
+            .map_err(|_| DisplayError::BusWriteError)?;
+
+        if rest.is_empty() {
+            Ok(())
+        } else {
+            self.write_data(rest)
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Display interface convienience factory methods
+
+mod i2c;
+mod parallel;
+mod spi;
+mod spi3wire;
+
+pub use i2c::*;
+pub use parallel::*;
+pub use spi::*;
+pub use spi3wire::*;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// Interface used by the driver to communicate with the controller
+///
+/// Implemented for every [`WriteOnlyDataCommand`] interface from `display_interface` crate
+pub trait ControllerInterface {
+    /// Bytes sent by [`write_commands_and_data`](ControllerInterface::write_commands_and_data)
+    /// besides a few command bytes and the display data, e.g. I2C addresses and control bytes
+    ///
+    /// Used to decide whether resending unchanged bytes is cheaper than starting a new transfer
+    const TRANSFER_OVERHEAD: usize = 0;
+
+    /// Send command bytes
+    fn write_commands(&mut self, commands: &[u8]) -> Result<(), DisplayError>;
+
+    /// Send display data bytes
+    fn write_data(&mut self, data: &[u8]) -> Result<(), DisplayError>;
+
+    /// Send command bytes followed by display data bytes, in a single transaction if the interface supports it
+    fn write_commands_and_data(
+        &mut self,
+        commands: &[u8],
+        data: &[u8],
+    ) -> Result<(), DisplayError> {
+        self.write_commands(commands)?;
+        self.write_data(data)
+    }
+}
+
+impl<DI: WriteOnlyDataCommand> ControllerInterface for DI {
+    /// Address and control bytes of the command and data transactions of [`I2CInterface`],
+    /// SPI and parallel interfaces send less but are faster
+    const TRANSFER_OVERHEAD: usize = 4;
+
+    fn write_commands(&mut self, commands: &[u8]) -> Result<(), DisplayError> {
+        self.send_commands(DataFormat::U8(commands))
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), DisplayError> {
+        self.send_data(DataFormat::U8(data))
+    }
+}
+
+/// Interface able to read from the controller, e.g. [`Parallel8080Interface`] or [`Parallel6800Interface`]
+pub trait ReadInterface {
+    /// Read the status byte (A0 = 0)
+    fn read_status(&mut self) -> Result<u8, DisplayError>;
+
+    /// Read display data bytes (A0 = 1) from the current RAM address
+    fn read_data(&mut self, data: &mut [u8]) -> Result<(), DisplayError>;
+}
+
+/// Call `f` for every byte of `U8` and `U8Iter` data formats
+fn for_each_byte(
+    bytes: DataFormat<'_>,
+    mut f: impl FnMut(u8) -> Result<(), DisplayError>,
+) -> Result<(), DisplayError> {
+    match bytes {
+        DataFormat::U8(slice) => slice.iter().try_for_each(|&byte| f(byte)),
+        DataFormat::U8Iter(iter) => {
+            for byte in iter {
+                f(byte)?;
+            }
+            Ok(())
+        }
+        _ => Err(DisplayError::DataFormatNotImplemented),
+    }
+}
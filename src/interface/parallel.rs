@@ -0,0 +1,286 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+
+use super::{for_each_byte, ReadInterface};
+
+/// 8-bit bidirectional data bus D0-D7
+pub trait DataBus {
+    /// Drive the bus with the given value
+    fn write(&mut self, value: u8) -> Result<(), DisplayError>;
+
+    /// Stop driving the bus so the controller can drive it
+    fn release(&mut self) -> Result<(), DisplayError>;
+
+    /// Sample the value driven by the controller
+    fn read(&mut self) -> Result<u8, DisplayError>;
+}
+
+/// Data bus of 8 open-drain GPIO pins, `pins[0]` is D0
+///
+/// The pins are released by setting them high, so they must be configured as open-drain
+/// outputs with pull-ups, or otherwise tolerate the controller driving them while set high
+impl<P: OutputPin + InputPin> DataBus for [P; 8] {
+    fn write(&mut self, value: u8) -> Result<(), DisplayError> {
+        self.iter_mut().enumerate().try_for_each(|(bit, pin)| {
+            if value & (1 << bit) != 0 {
+                pin.set_high()
+            } else {
+                pin.set_low()
+            }
+            .map_err(|_| DisplayError::BusWriteError)
+        })
+    }
+
+    fn release(&mut self) -> Result<(), DisplayError> {
+        self.write(0xff)
+    }
+
+    fn read(&mut self) -> Result<u8, DisplayError> {
+        self.iter().enumerate().try_fold(0, |value, (bit, pin)| {
+            let high = pin.is_high().map_err(|_| DisplayError::BusWriteError)?;
+            Ok(value | (u8::from(high) << bit))
+        })
+    }
+}
+
+/// 8080-series parallel interface over GPIO pins (C86 = "L")
+///
+/// Data is written on the rising edge of /WR and read while /RD is low.
+/// Bus errors while reading are reported as [`DisplayError::BusWriteError`]
+pub struct Parallel8080Interface<BUS, CS, A0, WR, RD> {
+    bus: BUS,
+    cs: CS,
+    a0: A0,
+    wr: WR,
+    rd: RD,
+}
+
+impl<BUS, CS, A0, WR, RD> Parallel8080Interface<BUS, CS, A0, WR, RD>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    /// Create a new interface
+    ///
+    /// /WR and /RD pins should be set high before the first transfer
+    pub fn new(bus: BUS, cs: CS, a0: A0, wr: WR, rd: RD) -> Self {
+        Parallel8080Interface {
+            bus,
+            cs,
+            a0,
+            wr,
+            rd,
+        }
+    }
+
+    /// Release the data bus and the control pins
+    pub fn release(self) -> (BUS, CS, A0, WR, RD) {
+        (self.bus, self.cs, self.a0, self.wr, self.rd)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), DisplayError> {
+        self.wr.set_low().map_err(|_| DisplayError::BusWriteError)?;
+        self.bus.write(byte)?;
+        self.wr.set_high().map_err(|_| DisplayError::BusWriteError)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, DisplayError> {
+        self.rd.set_low().map_err(|_| DisplayError::BusWriteError)?;
+        let byte = self.bus.read();
+        self.rd
+            .set_high()
+            .map_err(|_| DisplayError::BusWriteError)?;
+        byte
+    }
+
+    fn transfer<T>(
+        &mut self,
+        a0: bool,
+        f: impl FnOnce(&mut Self) -> Result<T, DisplayError>,
+    ) -> Result<T, DisplayError> {
+        if a0 {
+            self.a0.set_high()
+        } else {
+            self.a0.set_low()
+        }
+        .map_err(|_| DisplayError::DCError)?;
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+
+        let result = f(self);
+
+        self.cs.set_high().map_err(|_| DisplayError::CSError)?;
+
+        result
+    }
+
+    fn send(&mut self, a0: bool, bytes: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.transfer(a0, |interface| {
+            for_each_byte(bytes, |byte| interface.write_byte(byte))
+        })
+    }
+}
+
+impl<BUS, CS, A0, WR, RD> WriteOnlyDataCommand for Parallel8080Interface<BUS, CS, A0, WR, RD>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
+
+impl<BUS, CS, A0, WR, RD> ReadInterface for Parallel8080Interface<BUS, CS, A0, WR, RD>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        self.transfer(false, |interface| {
+            interface.bus.release()?;
+            interface.read_byte()
+        })
+    }
+
+    fn read_data(&mut self, data: &mut [u8]) -> Result<(), DisplayError> {
+        self.transfer(true, |interface| {
+            interface.bus.release()?;
+            data.iter_mut().try_for_each(|byte| {
+                *byte = interface.read_byte()?;
+                Ok(())
+            })
+        })
+    }
+}
+
+/// 6800-series parallel interface over GPIO pins (C86 = "H")
+///
+/// Data is written on the falling edge of E and read while E is high.
+/// Bus errors while reading are reported as [`DisplayError::BusWriteError`]
+pub struct Parallel6800Interface<BUS, CS, A0, RW, E> {
+    bus: BUS,
+    cs: CS,
+    a0: A0,
+    rw: RW,
+    e: E,
+}
+
+impl<BUS, CS, A0, RW, E> Parallel6800Interface<BUS, CS, A0, RW, E>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    RW: OutputPin,
+    E: OutputPin,
+{
+    /// Create a new interface
+    ///
+    /// E pin should be set low before the first transfer
+    pub fn new(bus: BUS, cs: CS, a0: A0, rw: RW, e: E) -> Self {
+        Parallel6800Interface { bus, cs, a0, rw, e }
+    }
+
+    /// Release the data bus and the control pins
+    pub fn release(self) -> (BUS, CS, A0, RW, E) {
+        (self.bus, self.cs, self.a0, self.rw, self.e)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), DisplayError> {
+        self.e.set_high().map_err(|_| DisplayError::BusWriteError)?;
+        self.bus.write(byte)?;
+        self.e.set_low().map_err(|_| DisplayError::BusWriteError)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, DisplayError> {
+        self.e.set_high().map_err(|_| DisplayError::BusWriteError)?;
+        let byte = self.bus.read();
+        self.e.set_low().map_err(|_| DisplayError::BusWriteError)?;
+        byte
+    }
+
+    fn transfer<T>(
+        &mut self,
+        a0: bool,
+        read: bool,
+        f: impl FnOnce(&mut Self) -> Result<T, DisplayError>,
+    ) -> Result<T, DisplayError> {
+        if a0 {
+            self.a0.set_high()
+        } else {
+            self.a0.set_low()
+        }
+        .map_err(|_| DisplayError::DCError)?;
+        if read {
+            self.bus.release()?;
+            self.rw.set_high()
+        } else {
+            self.rw.set_low()
+        }
+        .map_err(|_| DisplayError::BusWriteError)?;
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+
+        let result = f(self);
+
+        self.cs.set_high().map_err(|_| DisplayError::CSError)?;
+
+        result
+    }
+
+    fn send(&mut self, a0: bool, bytes: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.transfer(a0, false, |interface| {
+            for_each_byte(bytes, |byte| interface.write_byte(byte))
+        })
+    }
+}
+
+impl<BUS, CS, A0, RW, E> WriteOnlyDataCommand for Parallel6800Interface<BUS, CS, A0, RW, E>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    RW: OutputPin,
+    E: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
+
+impl<BUS, CS, A0, RW, E> ReadInterface for Parallel6800Interface<BUS, CS, A0, RW, E>
+where
+    BUS: DataBus,
+    CS: OutputPin,
+    A0: OutputPin,
+    RW: OutputPin,
+    E: OutputPin,
+{
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        self.transfer(false, true, |interface| interface.read_byte())
+    }
+
+    fn read_data(&mut self, data: &mut [u8]) -> Result<(), DisplayError> {
+        self.transfer(true, true, |interface| {
+            data.iter_mut().try_for_each(|byte| {
+                *byte = interface.read_byte()?;
+                Ok(())
+            })
+        })
+    }
+}
@@ -0,0 +1,30 @@
+pub use display_interface_spi::{SPIInterface, SPIInterfaceNoCS};
+
+use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+
+/// Wrapper for creating a 4-line SPIInterface with device-specific parameters
+///
+/// The D/C pin is connected to the A0 pin of the controller: low for commands, high for display data
+pub struct SPIDisplayInterface;
+
+impl SPIDisplayInterface {
+    /// Create a new SPIInterface for the ST7567S display
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<SPI, DC, CS>(spi: SPI, dc: DC, cs: CS) -> SPIInterface<SPI, DC, CS>
+    where
+        SPI: Write<u8>,
+        DC: OutputPin,
+        CS: OutputPin,
+    {
+        SPIInterface::new(spi, dc, cs)
+    }
+
+    /// Create a new SPIInterface for the ST7567S display with the CS pin tied low or managed by the SPI peripheral
+    pub fn new_no_cs<SPI, DC>(spi: SPI, dc: DC) -> SPIInterfaceNoCS<SPI, DC>
+    where
+        SPI: Write<u8>,
+        DC: OutputPin,
+    {
+        SPIInterfaceNoCS::new(spi, dc)
+    }
+}
@@ -0,0 +1,146 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+
+use super::for_each_byte;
+
+/// Number of 9-bit words sent to the SPI peripheral at once
+const WORDS_CHUNK_SIZE: usize = 32;
+
+/// 3-line SPI interface using a SPI peripheral configured for 9-bit words
+///
+/// Each word contains the A0 bit (0 for commands, 1 for display data) followed by the data byte,
+/// e.g. `0x1a5` is a display data byte `0xa5`. The peripheral must send words MSB first
+pub struct SPI3WireInterface<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> SPI3WireInterface<SPI, CS>
+where
+    SPI: Write<u16>,
+    CS: OutputPin,
+{
+    /// Create a new interface
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        SPI3WireInterface { spi, cs }
+    }
+
+    /// Release the SPI peripheral and the CS pin
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+
+    fn send(&mut self, a0: bool, bytes: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+
+        let mut words = [0; WORDS_CHUNK_SIZE];
+        let mut len = 0;
+        let result = for_each_byte(bytes, |byte| {
+            words[len] = (u16::from(a0) << 8) | u16::from(byte);
+            len += 1;
+            if len == words.len() {
+                len = 0;
+                self.spi
+                    .write(&words)
+                    .map_err(|_| DisplayError::BusWriteError)
+            } else {
+                Ok(())
+            }
+        })
+        .and_then(|_| match len {
+            0 => Ok(()),
+            _ => self
+                .spi
+                .write(&words[..len])
+                .map_err(|_| DisplayError::BusWriteError),
+        });
+
+        self.cs.set_high().map_err(|_| DisplayError::CSError)?;
+
+        result
+    }
+}
+
+impl<SPI, CS> WriteOnlyDataCommand for SPI3WireInterface<SPI, CS>
+where
+    SPI: Write<u16>,
+    CS: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
+
+/// 3-line SPI interface bit-banged over GPIO pins
+///
+/// Each byte is prefixed with the A0 bit (0 for commands, 1 for display data),
+/// bits are latched by the controller on the rising edge of SCL
+pub struct BitBangSPI3WireInterface<SCL, SDA, CS> {
+    scl: SCL,
+    sda: SDA,
+    cs: CS,
+}
+
+impl<SCL, SDA, CS> BitBangSPI3WireInterface<SCL, SDA, CS>
+where
+    SCL: OutputPin,
+    SDA: OutputPin,
+    CS: OutputPin,
+{
+    /// Create a new interface
+    pub fn new(scl: SCL, sda: SDA, cs: CS) -> Self {
+        BitBangSPI3WireInterface { scl, sda, cs }
+    }
+
+    /// Release the pins
+    pub fn release(self) -> (SCL, SDA, CS) {
+        (self.scl, self.sda, self.cs)
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), DisplayError> {
+        self.scl
+            .set_low()
+            .map_err(|_| DisplayError::BusWriteError)?;
+        if bit {
+            self.sda.set_high()
+        } else {
+            self.sda.set_low()
+        }
+        .map_err(|_| DisplayError::BusWriteError)?;
+        self.scl.set_high().map_err(|_| DisplayError::BusWriteError)
+    }
+
+    fn send(&mut self, a0: bool, bytes: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+
+        let result = for_each_byte(bytes, |byte| {
+            self.write_bit(a0)?;
+            (0..8)
+                .rev()
+                .try_for_each(|bit| self.write_bit(byte & (1 << bit) != 0))
+        });
+
+        self.cs.set_high().map_err(|_| DisplayError::CSError)?;
+
+        result
+    }
+}
+
+impl<SCL, SDA, CS> WriteOnlyDataCommand for BitBangSPI3WireInterface<SCL, SDA, CS>
+where
+    SCL: OutputPin,
+    SDA: OutputPin,
+    CS: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
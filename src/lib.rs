@@ -5,9 +5,14 @@
 //! # Features
 //!
 //! - Supports I2C and SPI communication protocols via the [`display_interface`](https://docs.rs/display_interface) crate.
-//! - Provides two display modes:
+//! - Provides [`NativeI2CInterface`](crate::interface::NativeI2CInterface) which sends page address commands together with display data in a single I2C transaction.
+//! - Supports 3-line 9-bit SPI via [`SPI3WireInterface`](crate::interface::SPI3WireInterface) for SPI peripherals with 9-bit words or [`BitBangSPI3WireInterface`](crate::interface::BitBangSPI3WireInterface) over GPIO pins.
+//! - Supports 8080 and 6800 parallel interfaces over GPIO pins via [`Parallel8080Interface`](crate::interface::Parallel8080Interface) and [`Parallel6800Interface`](crate::interface::Parallel6800Interface), including status and display data reads and unbuffered `set_pixel` using read-modify-write mode.
+//! - Provides three display modes:
 //!   - Direct Write Mode (by default): This mode allows you to write directly to the display memory by calling the [`draw`] method.
-//!   - Buffered Mode: This mode allows you to modify an internal buffer by using methods like [`set_pixel`], [`clear`], or by using the [`embedded-graphics`] crate. Once you have made your changes, you can call the [`flush`] method to write the buffer to the display.
+//!   - Buffered Mode: This mode allows you to modify an internal buffer by using methods like [`set_pixel`], [`clear`], or by using the [`embedded-graphics`] crate. Once you have made your changes, you can call the [`flush`] method to write the changed area of the buffer to the display.
+//!   - Diff Buffered Mode: Same as Buffered Mode, but [`DiffBufferedMode`](crate::display::DiffBufferedMode) keeps a copy of the last flushed buffer and sends only the bytes that differ from it, which also allows modifying the buffer directly.
+//! - Buffered Mode can use a buffer provided by the user with [`into_buffered_graphics_mode_with_buffer`](crate::display::ST7567S::into_buffered_graphics_mode_with_buffer), e.g. a `static` or a buffer placed in a specific memory region.
 //!
 //! [`embedded-graphics`]: https://docs.rs/embedded-graphics
 //! [`set_pixel`]: crate::display::ST7567S#method.set_pixel
@@ -16,8 +21,8 @@
 //! [`draw`]: crate::display::ST7567S#method.draw
 //!
 //! # Notes
-//! - This driver is designed to work with a more common 128x64 resolution, instead of the original 132x65 resolution of the ST7567S controller.
-//! - SPI communication is not tested yet.
+//! - This driver is designed to work with a more common 128x64 resolution by default, the original 132x65 resolution of the ST7567S controller can be selected with [`DisplaySize132x65`](crate::size::DisplaySize132x65), which requires normal COM direction to keep the icon line below the other lines.
+//! - SPI communication is tested only against a mock bus.
 //!
 //! # Examples
 //!
@@ -91,10 +96,12 @@
 
 #![no_std]
 
-mod command;
+pub mod command;
+pub mod config;
 mod consts;
 pub mod display;
 #[cfg(feature = "graphics")]
 pub mod graphics;
 pub mod interface;
 pub mod prelude;
+pub mod size;
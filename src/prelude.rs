@@ -1,6 +1,7 @@
 //! Prelude
 
 pub use crate::{
+    config::DisplayConfig,
     display::{DirectWriteMode, ST7567S},
-    interface::{I2CDisplayInterface, I2CInterface},
+    interface::{I2CDisplayInterface, I2CInterface, SPIDisplayInterface, SPIInterface},
 };
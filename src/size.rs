@@ -0,0 +1,170 @@
+//! Display size
+
+use core::marker::PhantomData;
+
+use crate::consts::{RAM_LINES, RAM_WIDTH};
+
+/// Panel size connected to the controller
+///
+/// The driver addresses `WIDTH` columns starting from SEG output `COLUMN_OFFSET`
+/// and `HEIGHT` lines starting from the first COM output.
+/// Panels of other sizes can be supported by implementing this trait:
+///
+/// ```rust
+/// use st7567s::size::DisplaySize;
+///
+/// /// 128x64 panel wired to SEG2-SEG129
+/// struct CenteredDisplaySize128x64;
+///
+/// impl DisplaySize for CenteredDisplaySize128x64 {
+///     const WIDTH: u8 = 128;
+///     const HEIGHT: u8 = 64;
+///     const COLUMN_OFFSET: u8 = 2;
+///     type Buffer = [u8; 128 * 64 / 8];
+/// }
+/// ```
+///
+/// The constants are checked when the driver is created, so the build fails if the panel
+/// doesn't fit the controller RAM, its height is not a multiple of 8 or 65,
+/// or `Buffer` is not `BUFFER_SIZE` bytes long:
+///
+/// ```compile_fail
+/// # use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+/// # use st7567s::{display::ST7567S, size::DisplaySize};
+/// # struct Interface;
+/// # impl WriteOnlyDataCommand for Interface {
+/// #     fn send_commands(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+/// #     fn send_data(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+/// # }
+/// struct TooWideDisplaySize;
+///
+/// impl DisplaySize for TooWideDisplaySize {
+///     const WIDTH: u8 = 128;
+///     const HEIGHT: u8 = 64;
+///     const COLUMN_OFFSET: u8 = 8;
+///     type Buffer = [u8; 128 * 64 / 8];
+/// }
+///
+/// let display = ST7567S::with_size(Interface, TooWideDisplaySize);
+/// ```
+///
+/// ```compile_fail
+/// # use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+/// # use st7567s::{display::ST7567S, size::DisplaySize};
+/// # struct Interface;
+/// # impl WriteOnlyDataCommand for Interface {
+/// #     fn send_commands(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+/// #     fn send_data(&mut self, _: DataFormat<'_>) -> Result<(), DisplayError> { Ok(()) }
+/// # }
+/// struct PartialPageDisplaySize;
+///
+/// impl DisplaySize for PartialPageDisplaySize {
+///     const WIDTH: u8 = 128;
+///     const HEIGHT: u8 = 60;
+///     type Buffer = [u8; 128 * 8];
+/// }
+///
+/// let display = ST7567S::with_size(Interface, PartialPageDisplaySize);
+/// ```
+pub trait DisplaySize {
+    /// Width in pixels (up to 132 including offset)
+    const WIDTH: u8;
+    /// Height in pixels (up to 65), multiple of 8 or 65
+    const HEIGHT: u8;
+    /// First SEG output the panel is wired to
+    const COLUMN_OFFSET: u8 = 0;
+    /// Buffer used by buffered mode, one byte per column for every 8 lines
+    type Buffer: AsMut<[u8]> + AsRef<[u8]> + NewZeroed;
+
+    /// Number of 8 lines high pages, must not be overridden
+    const PAGES: u8 = Self::HEIGHT.div_ceil(8);
+    /// Buffer size in bytes, must not be overridden
+    const BUFFER_SIZE: usize = Self::WIDTH as usize * Self::PAGES as usize;
+}
+
+/// Compile time check of the display size constants, evaluated when the driver is created
+pub(crate) struct SizeCheck<SIZE>(PhantomData<SIZE>);
+
+impl<SIZE: DisplaySize> SizeCheck<SIZE> {
+    pub(crate) const OK: () = {
+        assert!(
+            SIZE::WIDTH > 0 && SIZE::HEIGHT > 0,
+            "display size must not be zero"
+        );
+        assert!(
+            SIZE::WIDTH as u16 + SIZE::COLUMN_OFFSET as u16 <= RAM_WIDTH as u16,
+            "display doesn't fit into the controller RAM width"
+        );
+        assert!(
+            SIZE::HEIGHT <= RAM_LINES + 1,
+            "display doesn't fit into the controller RAM height"
+        );
+        assert!(
+            SIZE::HEIGHT % 8 == 0 || SIZE::HEIGHT == RAM_LINES + 1,
+            "display height must be a multiple of 8 or 65"
+        );
+        assert!(
+            SIZE::PAGES == SIZE::HEIGHT.div_ceil(8)
+                && SIZE::BUFFER_SIZE == SIZE::WIDTH as usize * SIZE::PAGES as usize,
+            "PAGES and BUFFER_SIZE must not be overridden"
+        );
+        assert!(
+            core::mem::size_of::<SIZE::Buffer>() == SIZE::BUFFER_SIZE,
+            "buffer length must be BUFFER_SIZE"
+        );
+    };
+}
+
+/// Creation of a zero-filled buffer
+pub trait NewZeroed {
+    /// Create zero-filled buffer
+    fn new_zeroed() -> Self;
+}
+
+impl<const N: usize> NewZeroed for [u8; N] {
+    fn new_zeroed() -> Self {
+        [0; N]
+    }
+}
+
+/// 128x64 panel, the most common one
+pub struct DisplaySize128x64;
+
+impl DisplaySize for DisplaySize128x64 {
+    const WIDTH: u8 = 128;
+    const HEIGHT: u8 = 64;
+    type Buffer = [u8; 128 * 64 / 8];
+}
+
+/// 128x32 panel
+pub struct DisplaySize128x32;
+
+impl DisplaySize for DisplaySize128x32 {
+    const WIDTH: u8 = 128;
+    const HEIGHT: u8 = 32;
+    type Buffer = [u8; 128 * 32 / 8];
+}
+
+/// 96x64 panel
+pub struct DisplaySize96x64;
+
+impl DisplaySize for DisplaySize96x64 {
+    const WIDTH: u8 = 96;
+    const HEIGHT: u8 = 64;
+    type Buffer = [u8; 96 * 64 / 8];
+}
+
+/// 132x65 panel using the full controller resolution, including the icon line as the last line
+///
+/// The icon line is always shown below COM63, so it follows the other lines only with normal COM direction
+/// sent to the controller. Use [`COMDirection::Normal`](crate::config::COMDirection::Normal) for 0° and 90°
+/// or [`COMDirection::Reverse`](crate::config::COMDirection::Reverse) for 180° and 270° rotation,
+/// init and [`set_rotation`](crate::display::ST7567S::set_rotation) return
+/// [`DisplayError::OutOfBoundsError`](display_interface::DisplayError::OutOfBoundsError) otherwise
+pub struct DisplaySize132x65;
+
+impl DisplaySize for DisplaySize132x65 {
+    const WIDTH: u8 = 132;
+    const HEIGHT: u8 = 65;
+    type Buffer = [u8; 132 * 9];
+}
@@ -0,0 +1,306 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use st7567s::{
+    display::{DiffBufferedMode, ST7567S},
+    size::DisplaySize128x64,
+};
+
+/// Bytes sent in a single `send_commands` or `send_data` call
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Transfer {
+    Commands(Vec<u8>),
+    Data(Vec<u8>),
+}
+
+type Transfers = Rc<RefCell<Vec<Transfer>>>;
+
+struct MockInterface(Transfers);
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            DataFormat::U8(bytes) => {
+                self.0.borrow_mut().push(Transfer::Commands(bytes.to_vec()));
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            DataFormat::U8(bytes) => {
+                self.0.borrow_mut().push(Transfer::Data(bytes.to_vec()));
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+fn take_transfers(transfers: &Transfers) -> Vec<Transfer> {
+    core::mem::take(&mut transfers.borrow_mut())
+}
+
+/// Number of display data bytes sent
+fn data_len(transfers: &[Transfer]) -> usize {
+    transfers
+        .iter()
+        .map(|transfer| match transfer {
+            Transfer::Data(data) => data.len(),
+            Transfer::Commands(_) => 0,
+        })
+        .sum()
+}
+
+#[test]
+fn flush_sends_only_changed_area() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone())).into_buffered_graphics_mode();
+    display.init().unwrap();
+    display.flush().unwrap();
+    take_transfers(&transfers);
+
+    display.set_pixel(10, 20, true).unwrap();
+    display.set_pixel(12, 30, true).unwrap();
+    display.flush().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb2, 0x0a, 0x10]),
+            Transfer::Data(vec![0x10, 0x00, 0x00]),
+            Transfer::Commands(vec![0xb3, 0x0a, 0x10]),
+            Transfer::Data(vec![0x00, 0x00, 0x40]),
+        ]
+    );
+
+    display.flush().unwrap();
+    assert!(take_transfers(&transfers).is_empty());
+}
+
+#[test]
+fn flush_after_reinit_sends_whole_buffer() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone())).into_buffered_graphics_mode();
+    display.init().unwrap();
+    display.set_pixel(10, 20, true).unwrap();
+    display.flush().unwrap();
+
+    display.init().unwrap();
+    take_transfers(&transfers);
+    display.flush().unwrap();
+
+    let transfers = take_transfers(&transfers);
+    assert_eq!(data_len(&transfers), 128 * 64 / 8);
+    assert!(transfers.contains(&Transfer::Commands(vec![0xb2, 0x00, 0x10])));
+}
+
+#[test]
+fn flush_after_draw_sends_whole_buffer() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone())).into_buffered_graphics_mode();
+    display.init().unwrap();
+    display.flush().unwrap();
+
+    display.draw(&[0xff; 128 * 64 / 8]).unwrap();
+    take_transfers(&transfers);
+    display.flush().unwrap();
+
+    assert_eq!(data_len(&take_transfers(&transfers)), 128 * 64 / 8);
+}
+
+#[test]
+fn flush_after_hardware_reset_sends_whole_buffer() {
+    struct NoopPin;
+
+    impl embedded_hal::digital::v2::OutputPin for NoopPin {
+        type Error = ();
+
+        fn set_low(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    struct NoopDelay;
+
+    impl embedded_hal::blocking::delay::DelayUs<u8> for NoopDelay {
+        fn delay_us(&mut self, _us: u8) {}
+    }
+
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone())).into_buffered_graphics_mode();
+    display.init().unwrap();
+    display.flush().unwrap();
+
+    display.reset_hw(&mut NoopPin, &mut NoopDelay).unwrap();
+    take_transfers(&transfers);
+    display.flush().unwrap();
+
+    assert_eq!(data_len(&take_transfers(&transfers)), 128 * 64 / 8);
+}
+
+fn diff_display(
+    transfers: &Transfers,
+) -> ST7567S<MockInterface, DiffBufferedMode, DisplaySize128x64> {
+    let mut display =
+        ST7567S::new(MockInterface(transfers.clone())).into_diff_buffered_graphics_mode();
+    display.init().unwrap();
+    display.flush().unwrap();
+    take_transfers(transfers);
+    display
+}
+
+#[test]
+fn diff_first_flush_sends_whole_buffer() {
+    let transfers = Transfers::default();
+    let mut display =
+        ST7567S::new(MockInterface(transfers.clone())).into_diff_buffered_graphics_mode();
+
+    display.flush().unwrap();
+
+    let transfers = take_transfers(&transfers);
+    assert_eq!(data_len(&transfers), 128 * 64 / 8);
+    assert_eq!(transfers[0], Transfer::Commands(vec![0xb0, 0x00, 0x10]));
+}
+
+#[test]
+fn diff_flush_without_changes_sends_nothing() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+
+    display.set_pixel(10, 20, false).unwrap();
+    display.flush().unwrap();
+
+    assert!(take_transfers(&transfers).is_empty());
+}
+
+#[test]
+fn diff_close_runs_are_merged() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+
+    // 6 unchanged bytes cost as much as column address commands and the transfer overhead
+    display.set_pixel(10, 20, true).unwrap();
+    display.set_pixel(17, 20, true).unwrap();
+    display.flush().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb2, 0x0a, 0x10]),
+            Transfer::Data(vec![0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10]),
+        ]
+    );
+}
+
+#[test]
+fn diff_distant_runs_are_split_with_column_address_only() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+
+    display.set_pixel(10, 20, true).unwrap();
+    display.set_pixel(18, 20, true).unwrap();
+    display.set_pixel(10, 30, true).unwrap();
+    display.flush().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb2, 0x0a, 0x10]),
+            Transfer::Data(vec![0x10]),
+            Transfer::Commands(vec![0x02, 0x11]),
+            Transfer::Data(vec![0x10]),
+            Transfer::Commands(vec![0xb3, 0x0a, 0x10]),
+            Transfer::Data(vec![0x40]),
+        ]
+    );
+}
+
+#[test]
+fn diff_run_at_last_column() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+
+    display.set_pixel(125, 0, true).unwrap();
+    display.set_pixel(127, 0, true).unwrap();
+    display.flush().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb0, 0x0d, 0x17]),
+            Transfer::Data(vec![0x01, 0x00, 0x01]),
+        ]
+    );
+}
+
+#[test]
+fn diff_detects_changes_made_through_buffer_mut() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+
+    display.buffer_mut()[128 + 5] = 0xaa;
+    display.flush().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb1, 0x05, 0x10]),
+            Transfer::Data(vec![0xaa]),
+        ]
+    );
+}
+
+#[test]
+fn diff_flush_after_reinit_sends_whole_buffer() {
+    let transfers = Transfers::default();
+    let mut display = diff_display(&transfers);
+    display.set_pixel(10, 20, true).unwrap();
+    display.flush().unwrap();
+
+    display.init().unwrap();
+    take_transfers(&transfers);
+    display.flush().unwrap();
+
+    assert_eq!(data_len(&take_transfers(&transfers)), 128 * 64 / 8);
+}
+
+#[test]
+fn buffered_mode_uses_borrowed_buffer() {
+    let transfers = Transfers::default();
+    let mut buffer = [0; 128 * 64 / 8];
+    buffer[128] = 0x81;
+
+    let mut display = ST7567S::new(MockInterface(transfers.clone()))
+        .into_buffered_graphics_mode_with_buffer(&mut buffer[..])
+        .unwrap();
+    display.flush().unwrap();
+    assert!(take_transfers(&transfers).contains(&Transfer::Data({
+        let mut page = vec![0; 128];
+        page[0] = 0x81;
+        page
+    })));
+
+    display.set_pixel(1, 0, true).unwrap();
+    display.flush().unwrap();
+    drop(display);
+
+    assert_eq!(buffer[1], 0x01);
+}
+
+#[test]
+fn buffered_mode_rejects_buffer_of_wrong_length() {
+    let mut buffer = [0; 128 * 64 / 8 - 1];
+
+    let result = ST7567S::new(MockInterface(Transfers::default()))
+        .into_buffered_graphics_mode_with_buffer(&mut buffer[..]);
+
+    assert!(matches!(result, Err(DisplayError::OutOfBoundsError)));
+}
@@ -0,0 +1,460 @@
+use core::cell::RefCell;
+use std::{collections::VecDeque, rc::Rc};
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use st7567s::{
+    config::{COMDirection, DisplayConfig, DisplayRotation, PowerControl},
+    display::{DirectWriteMode, ST7567S},
+    interface::ReadInterface,
+    size::{DisplaySize128x32, DisplaySize132x65},
+};
+
+/// Bytes sent in a single `send_commands` or `send_data` call, or returned by a single `read_data` call
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Transfer {
+    Commands(Vec<u8>),
+    Data(Vec<u8>),
+    Read(Vec<u8>),
+}
+
+type Transfers = Rc<RefCell<Vec<Transfer>>>;
+
+struct MockInterface(Transfers);
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            DataFormat::U8(bytes) => {
+                self.0.borrow_mut().push(Transfer::Commands(bytes.to_vec()));
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            DataFormat::U8(bytes) => {
+                self.0.borrow_mut().push(Transfer::Data(bytes.to_vec()));
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+fn take_transfers(transfers: &Transfers) -> Vec<Transfer> {
+    core::mem::take(&mut transfers.borrow_mut())
+}
+
+#[test]
+fn wake_restores_all_pixels_on_mode() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+    display.init().unwrap();
+    display.set_all_pixels_on(true).unwrap();
+    display.sleep().unwrap();
+    take_transfers(&transfers);
+
+    display.wake().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xa4]),
+            Transfer::Commands(vec![0xaf]),
+            Transfer::Commands(vec![0xa5]),
+        ]
+    );
+}
+
+#[test]
+fn wake_keeps_normal_mode() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+    display.init().unwrap();
+    display.set_all_pixels_on(true).unwrap();
+    display.set_all_pixels_on(false).unwrap();
+    display.sleep().unwrap();
+    take_transfers(&transfers);
+
+    display.wake().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xa4]),
+            Transfer::Commands(vec![0xaf]),
+        ]
+    );
+}
+
+#[test]
+fn shutdown_turns_display_and_power_off() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+
+    display.shutdown().unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xae]),
+            Transfer::Commands(vec![0x28]),
+            Transfer::Commands(vec![0xa5]),
+        ]
+    );
+}
+
+#[test]
+fn interface_is_released_after_failed_shutdown() {
+    struct FailingInterface;
+
+    impl WriteOnlyDataCommand for FailingInterface {
+        fn send_commands(&mut self, _cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            Err(DisplayError::BusWriteError)
+        }
+
+        fn send_data(&mut self, _buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            Err(DisplayError::BusWriteError)
+        }
+    }
+
+    let mut display = ST7567S::new(FailingInterface);
+
+    assert!(matches!(
+        display.shutdown(),
+        Err(DisplayError::BusWriteError)
+    ));
+    let FailingInterface = display.release();
+}
+
+/// Records requested delays in milliseconds
+struct MockDelay(Vec<u8>);
+
+impl embedded_hal::blocking::delay::DelayMs<u8> for MockDelay {
+    fn delay_ms(&mut self, ms: u8) {
+        self.0.push(ms);
+    }
+}
+
+/// Power control commands, each sent in its own transfer
+fn power_control_commands(transfers: &[Transfer]) -> Vec<u8> {
+    transfers
+        .iter()
+        .filter_map(|transfer| match transfer {
+            Transfer::Commands(commands) if commands.len() == 1 => Some(commands[0]),
+            _ => None,
+        })
+        .filter(|command| command & 0xf8 == 0x28)
+        .collect()
+}
+
+#[test]
+fn power_up_turns_circuits_on_one_by_one() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+    let mut delay = MockDelay(Vec::new());
+
+    display
+        .init_with_delay(DisplayConfig::new(), &mut delay, 10)
+        .unwrap();
+
+    assert_eq!(
+        power_control_commands(&take_transfers(&transfers)),
+        [0x2c, 0x2e, 0x2f]
+    );
+    assert_eq!(delay.0, [10, 10, 10]);
+}
+
+#[test]
+fn power_up_skips_steps_without_changes_when_booster_is_off() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+    let mut delay = MockDelay(Vec::new());
+    let config = DisplayConfig::new().power_control(PowerControl {
+        booster: false,
+        ..PowerControl::ALL_ON
+    });
+
+    display.init_with_delay(config, &mut delay, 10).unwrap();
+
+    assert_eq!(
+        power_control_commands(&take_transfers(&transfers)),
+        [0x2a, 0x2b]
+    );
+    assert_eq!(delay.0, [10, 10]);
+}
+
+#[test]
+fn power_up_sends_nothing_when_all_circuits_are_off() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+    let mut delay = MockDelay(Vec::new());
+    let config = DisplayConfig::new().power_control(PowerControl::OFF);
+
+    display.init_with_delay(config, &mut delay, 10).unwrap();
+
+    assert!(power_control_commands(&take_transfers(&transfers)).is_empty());
+    assert!(delay.0.is_empty());
+}
+
+#[test]
+fn rotation_is_sent_in_a_single_transfer() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::new(MockInterface(transfers.clone()));
+
+    display.set_rotation(DisplayRotation::Rotate180).unwrap();
+    display.set_rotation(DisplayRotation::Rotate90).unwrap();
+
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xa1, 0xc0]),
+            Transfer::Commands(vec![0xa0, 0xc8]),
+        ]
+    );
+}
+
+#[cfg(feature = "graphics")]
+#[test]
+fn rotation_swaps_dimensions() {
+    use embedded_graphics::prelude::*;
+
+    let mut display =
+        ST7567S::new(MockInterface(Transfers::default())).into_diff_buffered_graphics_mode();
+
+    for (rotation, size) in [
+        (DisplayRotation::Rotate0, Size::new(128, 64)),
+        (DisplayRotation::Rotate90, Size::new(64, 128)),
+        (DisplayRotation::Rotate180, Size::new(128, 64)),
+        (DisplayRotation::Rotate270, Size::new(64, 128)),
+    ] {
+        display.set_rotation(rotation).unwrap();
+        assert_eq!(display.size(), size, "{rotation:?}");
+    }
+}
+
+#[test]
+fn corner_pixels_are_mapped_for_every_rotation() {
+    // Buffer byte index and bit of the top left, top right, bottom left and bottom right corners.
+    // 180° and 270° are flipped by the controller, so they share the buffer layout with 0° and 90°
+    let unrotated = [(0, 0), (127, 0), (7 * 128, 7), (7 * 128 + 127, 7)];
+    let transposed = [(127, 0), (7 * 128 + 127, 7), (0, 0), (7 * 128, 7)];
+
+    for (rotation, corners) in [
+        (DisplayRotation::Rotate0, unrotated),
+        (DisplayRotation::Rotate90, transposed),
+        (DisplayRotation::Rotate180, unrotated),
+        (DisplayRotation::Rotate270, transposed),
+    ] {
+        let mut display =
+            ST7567S::new(MockInterface(Transfers::default())).into_diff_buffered_graphics_mode();
+        display.set_rotation(rotation).unwrap();
+        let (width, height) = display.dimensions();
+
+        let points = [
+            (0, 0),
+            (width - 1, 0),
+            (0, height - 1),
+            (width - 1, height - 1),
+        ];
+        for ((x, y), (byte_idx, bit)) in points.into_iter().zip(corners) {
+            display.set_pixel(x, y, true).unwrap();
+
+            let set_bytes: Vec<_> = display
+                .buffer()
+                .iter()
+                .enumerate()
+                .filter(|(_, &byte)| byte != 0)
+                .map(|(idx, &byte)| (idx, byte))
+                .collect();
+            assert_eq!(set_bytes, [(byte_idx, 1 << bit)], "{rotation:?} ({x}, {y})");
+
+            display.set_pixel(x, y, false).unwrap();
+        }
+    }
+}
+
+fn display_132x65(
+    transfers: &Transfers,
+) -> ST7567S<MockInterface, DirectWriteMode, DisplaySize132x65> {
+    let mut display = ST7567S::with_size(MockInterface(transfers.clone()), DisplaySize132x65);
+    display
+        .init_with(DisplayConfig::new().com_direction(COMDirection::Normal))
+        .unwrap();
+    take_transfers(transfers);
+    display
+}
+
+#[test]
+fn last_lines_of_132x65_are_on_com63_and_icon_line() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::with_size(MockInterface(transfers.clone()), DisplaySize132x65);
+
+    display
+        .init_with(DisplayConfig::new().com_direction(COMDirection::Normal))
+        .unwrap();
+    // COM0 -> COM63 direction
+    assert_eq!(
+        take_transfers(&transfers)[0],
+        Transfer::Commands(vec![0xa2, 0xa0, 0xc0, 0x24, 0x40])
+    );
+
+    let mut buffer = [0; 132 * 9];
+    buffer[7 * 132] = 0x80;
+    buffer[8 * 132] = 0x01;
+    display.bounded_draw(&buffer, (0, 63), (0, 64)).unwrap();
+
+    // Line 63 is the last bit of RAM page 7 shown on COM63, line 64 is the icon page shown on COMS
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb7, 0x00, 0x10]),
+            Transfer::Data(vec![0x80]),
+            Transfer::Commands(vec![0xb8, 0x00, 0x10]),
+            Transfer::Data(vec![0x01]),
+        ]
+    );
+}
+
+#[test]
+fn reversed_com_direction_is_rejected_for_132x65() {
+    let transfers = Transfers::default();
+    let mut display = ST7567S::with_size(MockInterface(transfers.clone()), DisplaySize132x65);
+
+    assert!(matches!(
+        display.init(),
+        Err(DisplayError::OutOfBoundsError)
+    ));
+    assert!(take_transfers(&transfers).is_empty());
+
+    display
+        .init_with(
+            DisplayConfig::new()
+                .com_direction(COMDirection::Reverse)
+                .rotation(DisplayRotation::Rotate180),
+        )
+        .unwrap();
+}
+
+#[test]
+fn rotation_reversing_com_direction_is_rejected_for_132x65() {
+    let transfers = Transfers::default();
+    let mut display = display_132x65(&transfers);
+
+    assert!(matches!(
+        display.set_rotation(DisplayRotation::Rotate180),
+        Err(DisplayError::OutOfBoundsError)
+    ));
+    assert_eq!(display.rotation(), DisplayRotation::Rotate0);
+    assert!(take_transfers(&transfers).is_empty());
+
+    display.set_rotation(DisplayRotation::Rotate90).unwrap();
+}
+
+/// Interface returning queued bytes on reads
+struct MockReadInterface {
+    interface: MockInterface,
+    reads: VecDeque<u8>,
+}
+
+impl WriteOnlyDataCommand for MockReadInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_data(buf)
+    }
+}
+
+impl ReadInterface for MockReadInterface {
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        Err(DisplayError::BusWriteError)
+    }
+
+    fn read_data(&mut self, data: &mut [u8]) -> Result<(), DisplayError> {
+        for byte in data.iter_mut() {
+            *byte = self.reads.pop_front().expect("unexpected read");
+        }
+        self.interface
+            .0
+            .borrow_mut()
+            .push(Transfer::Read(data.to_vec()));
+        Ok(())
+    }
+}
+
+fn read_display_128x32(
+    transfers: &Transfers,
+    rotation: DisplayRotation,
+    reads: [u8; 2],
+) -> ST7567S<MockReadInterface, DirectWriteMode, DisplaySize128x32> {
+    let interface = MockReadInterface {
+        interface: MockInterface(transfers.clone()),
+        reads: reads.into(),
+    };
+    let mut display = ST7567S::with_size(interface, DisplaySize128x32);
+    display.set_rotation(rotation).unwrap();
+    take_transfers(transfers);
+    display
+}
+
+#[test]
+fn direct_set_pixel_changes_only_target_bit() {
+    let transfers = Transfers::default();
+    // Latch contents, then the RAM byte
+    let mut display = read_display_128x32(&transfers, DisplayRotation::Rotate0, [0xff, 0x81]);
+
+    display.set_pixel(10, 12, true).unwrap();
+
+    // Reversed COM direction moves 32 lines to RAM pages 4-7
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb5, 0x0a, 0x10, 0xe0]),
+            Transfer::Read(vec![0xff, 0x81]),
+            Transfer::Data(vec![0x91]),
+            Transfer::Commands(vec![0xee]),
+        ]
+    );
+}
+
+#[test]
+fn direct_set_pixel_clears_only_target_bit() {
+    let transfers = Transfers::default();
+    let mut display = read_display_128x32(&transfers, DisplayRotation::Rotate180, [0x00, 0xff]);
+
+    display.set_pixel(10, 12, false).unwrap();
+
+    // Reversed SEG direction moves 128 columns to RAM columns 4-131, normal COM direction uses pages 0-3
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb1, 0x0e, 0x10, 0xe0]),
+            Transfer::Read(vec![0x00, 0xff]),
+            Transfer::Data(vec![0xef]),
+            Transfer::Commands(vec![0xee]),
+        ]
+    );
+}
+
+#[test]
+fn direct_set_pixel_maps_rotated_coordinates() {
+    let transfers = Transfers::default();
+    let mut display = read_display_128x32(&transfers, DisplayRotation::Rotate90, [0x00, 0x00]);
+
+    display.set_pixel(5, 100, true).unwrap();
+
+    // (5, 100) is column 127 - 100 = 27 of line 5
+    assert_eq!(
+        take_transfers(&transfers),
+        [
+            Transfer::Commands(vec![0xb4, 0x0b, 0x11, 0xe0]),
+            Transfer::Read(vec![0x00, 0x00]),
+            Transfer::Data(vec![0x20]),
+            Transfer::Commands(vec![0xee]),
+        ]
+    );
+}
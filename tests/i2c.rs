@@ -0,0 +1,194 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+
+use st7567s::{
+    command::*,
+    display::ST7567S,
+    interface::{ControllerInterface, I2CDisplayInterface, NativeI2CInterface},
+};
+
+/// Bytes written to the bus in a single I2C transaction
+type Transactions = Rc<RefCell<Vec<Vec<u8>>>>;
+
+struct MockI2c(Transactions);
+
+impl embedded_hal::blocking::i2c::Write for MockI2c {
+    type Error = ();
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), ()> {
+        assert_eq!(address, 0x3f);
+        self.0.borrow_mut().push(bytes.to_vec());
+        Ok(())
+    }
+}
+
+fn take_transactions(transactions: &Transactions) -> Vec<Vec<u8>> {
+    core::mem::take(&mut transactions.borrow_mut())
+}
+
+#[test]
+fn long_raw_commands_are_split_into_transactions() {
+    let transactions = Transactions::default();
+    let mut display = ST7567S::new(I2CDisplayInterface::new(MockI2c(transactions.clone())));
+
+    display.send_raw_commands(&[0xe3; 8]).unwrap();
+
+    assert_eq!(
+        take_transactions(&transactions),
+        [
+            vec![0x00, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3],
+            vec![0x00, 0xe3]
+        ]
+    );
+}
+
+#[test]
+fn double_byte_raw_commands_are_not_split() {
+    let transactions = Transactions::default();
+    let mut display = ST7567S::new(I2CDisplayInterface::new(MockI2c(transactions.clone())));
+
+    display
+        .send_raw_commands(&[0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0x81, 0x20])
+        .unwrap();
+
+    assert_eq!(
+        take_transactions(&transactions),
+        [
+            vec![0x00, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3],
+            vec![0x00, 0x81, 0x20]
+        ]
+    );
+}
+
+#[test]
+fn long_command_buffer_is_split_into_transactions() {
+    let transactions = Transactions::default();
+    let mut interface = I2CDisplayInterface::new(MockI2c(transactions.clone()));
+
+    let mut commands = CommandBuffer::<10>::new();
+    for _ in 0..4 {
+        commands.push(&NopCommand).unwrap();
+    }
+    commands
+        .push(&SetElectronicVolumeCommand::new(0x20).unwrap())
+        .unwrap();
+    commands.push(&DisplayOnCommand::On).unwrap();
+    commands.push(&NopCommand).unwrap();
+    commands.push(&NopCommand).unwrap();
+    commands.write(&mut interface).unwrap();
+
+    assert_eq!(
+        take_transactions(&transactions),
+        [
+            vec![0x00, 0xe3, 0xe3, 0xe3, 0xe3, 0x81, 0x20, 0xaf],
+            vec![0x00, 0xe3, 0xe3]
+        ]
+    );
+}
+
+#[test]
+fn command_buffer_keeps_custom_double_byte_commands_together() {
+    /// Command with a data byte not known to the driver
+    struct CustomCommand;
+
+    impl Command for CustomCommand {
+        fn command(&self) -> u8 {
+            0xd0
+        }
+
+        fn data(&self) -> Option<u8> {
+            Some(0x81)
+        }
+    }
+
+    let transactions = Transactions::default();
+    let mut interface = I2CDisplayInterface::new(MockI2c(transactions.clone()));
+
+    let mut commands = CommandBuffer::<10>::new();
+    for _ in 0..6 {
+        commands.push(&NopCommand).unwrap();
+    }
+    commands.push(&CustomCommand).unwrap();
+    commands.push(&NopCommand).unwrap();
+    commands.write(&mut interface).unwrap();
+
+    assert_eq!(
+        take_transactions(&transactions),
+        [
+            vec![0x00, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3, 0xe3],
+            vec![0x00, 0xd0, 0x81, 0xe3]
+        ]
+    );
+}
+
+#[test]
+fn native_page_flush_is_a_single_transaction() {
+    let transactions = Transactions::default();
+    let mut display = ST7567S::new(NativeI2CInterface::new(MockI2c(transactions.clone())));
+
+    let buffer = [0xa5; 128 * 64 / 8];
+    display.bounded_draw(&buffer, (0, 8), (127, 15)).unwrap();
+
+    let mut expected = vec![0x80, 0xb1, 0x80, 0x00, 0x80, 0x10, 0x40];
+    expected.extend([0xa5; 128]);
+    assert_eq!(take_transactions(&transactions), [expected]);
+}
+
+#[test]
+fn native_long_data_spills_into_data_transactions() {
+    let transactions = Transactions::default();
+    let mut interface = NativeI2CInterface::new(MockI2c(transactions.clone()));
+
+    let data: Vec<u8> = (0..300).map(|byte| byte as u8).collect();
+    interface
+        .write_commands_and_data(&[0xb0, 0x00, 0x10], &data)
+        .unwrap();
+
+    // Transfer buffer fits 8 packed commands, a data control byte and 132 data bytes
+    let transfer_buffer_size = 2 * 8 + 1 + 132;
+    let packed = transfer_buffer_size - 7;
+    let mut first = vec![0x80, 0xb0, 0x80, 0x00, 0x80, 0x10, 0x40];
+    first.extend(&data[..packed]);
+    let mut second = vec![0x40];
+    second.extend(&data[packed..packed + transfer_buffer_size - 1]);
+    let mut third = vec![0x40];
+    third.extend(&data[packed + transfer_buffer_size - 1..]);
+
+    let transactions = take_transactions(&transactions);
+    assert_eq!(transactions, [first, second, third]);
+    assert!(transactions
+        .iter()
+        .all(|transaction| transaction.len() <= transfer_buffer_size));
+}
+
+#[test]
+fn native_too_many_commands_are_not_packed() {
+    let transactions = Transactions::default();
+    let mut interface = NativeI2CInterface::new(MockI2c(transactions.clone()));
+
+    interface
+        .write_commands_and_data(&[0xe3; 9], &[0x01, 0x02])
+        .unwrap();
+
+    let mut commands = vec![0x00];
+    commands.extend([0xe3; 9]);
+    assert_eq!(
+        take_transactions(&transactions),
+        [commands, vec![0x40, 0x01, 0x02]]
+    );
+}
+
+#[test]
+fn native_commands_without_data_are_not_packed() {
+    let transactions = Transactions::default();
+    let mut interface = NativeI2CInterface::new(MockI2c(transactions.clone()));
+
+    interface
+        .write_commands_and_data(&[0xb0, 0x00, 0x10], &[])
+        .unwrap();
+
+    assert_eq!(
+        take_transactions(&transactions),
+        [vec![0x00, 0xb0, 0x00, 0x10]]
+    );
+}
@@ -0,0 +1,279 @@
+use core::cell::RefCell;
+use std::{collections::VecDeque, rc::Rc};
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use st7567s::{
+    display::{Status, ST7567S},
+    interface::{DataBus, Parallel6800Interface, Parallel8080Interface, ReadInterface},
+};
+
+/// Bus activity in the order it happened
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Event {
+    /// Control pin level change
+    Pin(&'static str, bool),
+    /// Value driven on the data bus
+    Write(u8),
+    /// Data bus released to the controller
+    Release,
+    /// Value sampled from the data bus
+    Read(u8),
+}
+
+#[derive(Default)]
+struct BusState {
+    /// Values driven by the controller on the following reads
+    reads: VecDeque<u8>,
+    events: Vec<Event>,
+}
+
+type Bus = Rc<RefCell<BusState>>;
+
+fn take_events(bus: &Bus) -> Vec<Event> {
+    core::mem::take(&mut bus.borrow_mut().events)
+}
+
+struct MockDataBus(Bus);
+
+impl DataBus for MockDataBus {
+    fn write(&mut self, value: u8) -> Result<(), DisplayError> {
+        self.0.borrow_mut().events.push(Event::Write(value));
+        Ok(())
+    }
+
+    fn release(&mut self) -> Result<(), DisplayError> {
+        self.0.borrow_mut().events.push(Event::Release);
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<u8, DisplayError> {
+        let mut bus = self.0.borrow_mut();
+        let value = bus.reads.pop_front().expect("unexpected read");
+        bus.events.push(Event::Read(value));
+        Ok(value)
+    }
+}
+
+struct MockPin(Bus, &'static str);
+
+impl embedded_hal::digital::v2::OutputPin for MockPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().events.push(Event::Pin(self.1, false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().events.push(Event::Pin(self.1, true));
+        Ok(())
+    }
+}
+
+type Interface8080 = Parallel8080Interface<MockDataBus, MockPin, MockPin, MockPin, MockPin>;
+type Interface6800 = Parallel6800Interface<MockDataBus, MockPin, MockPin, MockPin, MockPin>;
+
+fn interface_8080(bus: &Bus) -> Interface8080 {
+    Parallel8080Interface::new(
+        MockDataBus(bus.clone()),
+        MockPin(bus.clone(), "CS"),
+        MockPin(bus.clone(), "A0"),
+        MockPin(bus.clone(), "WR"),
+        MockPin(bus.clone(), "RD"),
+    )
+}
+
+fn interface_6800(bus: &Bus) -> Interface6800 {
+    Parallel6800Interface::new(
+        MockDataBus(bus.clone()),
+        MockPin(bus.clone(), "CS"),
+        MockPin(bus.clone(), "A0"),
+        MockPin(bus.clone(), "RW"),
+        MockPin(bus.clone(), "E"),
+    )
+}
+
+fn low(pin: &'static str) -> Event {
+    Event::Pin(pin, false)
+}
+
+fn high(pin: &'static str) -> Event {
+    Event::Pin(pin, true)
+}
+
+#[test]
+fn parallel_8080_writes_on_rising_edge_of_wr() {
+    let bus = Bus::default();
+    let mut interface = interface_8080(&bus);
+
+    interface.send_commands(DataFormat::U8(&[0xaf])).unwrap();
+    interface.send_data(DataFormat::U8(&[0x12, 0x34])).unwrap();
+
+    assert_eq!(
+        take_events(&bus),
+        [
+            low("A0"),
+            low("CS"),
+            low("WR"),
+            Event::Write(0xaf),
+            high("WR"),
+            high("CS"),
+            high("A0"),
+            low("CS"),
+            low("WR"),
+            Event::Write(0x12),
+            high("WR"),
+            low("WR"),
+            Event::Write(0x34),
+            high("WR"),
+            high("CS"),
+        ]
+    );
+}
+
+#[test]
+fn parallel_8080_releases_bus_before_reading() {
+    let bus = Bus::default();
+    bus.borrow_mut().reads.extend([0x12, 0x34]);
+    let mut interface = interface_8080(&bus);
+
+    let mut data = [0; 2];
+    interface.read_data(&mut data).unwrap();
+
+    assert_eq!(data, [0x12, 0x34]);
+    assert_eq!(
+        take_events(&bus),
+        [
+            high("A0"),
+            low("CS"),
+            Event::Release,
+            low("RD"),
+            Event::Read(0x12),
+            high("RD"),
+            low("RD"),
+            Event::Read(0x34),
+            high("RD"),
+            high("CS"),
+        ]
+    );
+}
+
+#[test]
+fn parallel_8080_status_is_read_with_a0_low() {
+    let bus = Bus::default();
+    bus.borrow_mut().reads.push_back(0xc0);
+    let mut display = ST7567S::new(interface_8080(&bus));
+
+    let status = display.read_status().unwrap();
+
+    assert_eq!(
+        status,
+        Status {
+            busy: true,
+            seg_reversed: true,
+            display_on: true,
+            resetting: false,
+        }
+    );
+    assert_eq!(
+        take_events(&bus),
+        [
+            low("A0"),
+            low("CS"),
+            Event::Release,
+            low("RD"),
+            Event::Read(0xc0),
+            high("RD"),
+            high("CS"),
+        ]
+    );
+}
+
+#[test]
+fn parallel_6800_writes_on_falling_edge_of_e() {
+    let bus = Bus::default();
+    let mut interface = interface_6800(&bus);
+
+    interface.send_commands(DataFormat::U8(&[0xaf])).unwrap();
+    interface.send_data(DataFormat::U8(&[0x12])).unwrap();
+
+    assert_eq!(
+        take_events(&bus),
+        [
+            low("A0"),
+            low("RW"),
+            low("CS"),
+            high("E"),
+            Event::Write(0xaf),
+            low("E"),
+            high("CS"),
+            high("A0"),
+            low("RW"),
+            low("CS"),
+            high("E"),
+            Event::Write(0x12),
+            low("E"),
+            high("CS"),
+        ]
+    );
+}
+
+#[test]
+fn parallel_6800_releases_bus_before_reading() {
+    let bus = Bus::default();
+    bus.borrow_mut().reads.extend([0x12, 0x34]);
+    let mut interface = interface_6800(&bus);
+
+    let mut data = [0; 2];
+    interface.read_data(&mut data).unwrap();
+
+    assert_eq!(data, [0x12, 0x34]);
+    assert_eq!(
+        take_events(&bus),
+        [
+            high("A0"),
+            Event::Release,
+            high("RW"),
+            low("CS"),
+            high("E"),
+            Event::Read(0x12),
+            low("E"),
+            high("E"),
+            Event::Read(0x34),
+            low("E"),
+            high("CS"),
+        ]
+    );
+}
+
+#[test]
+fn parallel_6800_status_is_read_with_a0_low() {
+    let bus = Bus::default();
+    bus.borrow_mut().reads.push_back(0x30);
+    let mut display = ST7567S::new(interface_6800(&bus));
+
+    let status = display.read_status().unwrap();
+
+    assert_eq!(
+        status,
+        Status {
+            busy: false,
+            seg_reversed: false,
+            display_on: false,
+            resetting: true,
+        }
+    );
+    assert_eq!(
+        take_events(&bus),
+        [
+            low("A0"),
+            Event::Release,
+            high("RW"),
+            low("CS"),
+            high("E"),
+            Event::Read(0x30),
+            low("E"),
+            high("CS"),
+        ]
+    );
+}
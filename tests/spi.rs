@@ -0,0 +1,161 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+
+use st7567s::{display::ST7567S, interface::SPIDisplayInterface};
+
+/// Byte written to the bus with the levels of D/C and CS pins at that moment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Transfer {
+    byte: u8,
+    dc: bool,
+    cs: bool,
+}
+
+#[derive(Default)]
+struct BusState {
+    dc: bool,
+    cs: bool,
+    transfers: Vec<Transfer>,
+}
+
+type Bus = Rc<RefCell<BusState>>;
+
+struct MockSpi(Bus);
+
+impl embedded_hal::blocking::spi::Write<u8> for MockSpi {
+    type Error = ();
+
+    fn write(&mut self, words: &[u8]) -> Result<(), ()> {
+        let mut bus = self.0.borrow_mut();
+        let (dc, cs) = (bus.dc, bus.cs);
+        bus.transfers
+            .extend(words.iter().map(|&byte| Transfer { byte, dc, cs }));
+        Ok(())
+    }
+}
+
+struct MockDcPin(Bus);
+
+impl embedded_hal::digital::v2::OutputPin for MockDcPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().dc = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().dc = true;
+        Ok(())
+    }
+}
+
+struct MockCsPin(Bus);
+
+impl embedded_hal::digital::v2::OutputPin for MockCsPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().cs = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().cs = true;
+        Ok(())
+    }
+}
+
+fn bus() -> Bus {
+    Rc::new(RefCell::new(BusState {
+        cs: true,
+        ..Default::default()
+    }))
+}
+
+fn take_transfers(bus: &Bus) -> Vec<Transfer> {
+    core::mem::take(&mut bus.borrow_mut().transfers)
+}
+
+fn command(byte: u8) -> Transfer {
+    Transfer {
+        byte,
+        dc: false,
+        cs: false,
+    }
+}
+
+fn data(byte: u8) -> Transfer {
+    Transfer {
+        byte,
+        dc: true,
+        cs: false,
+    }
+}
+
+#[test]
+fn commands_are_sent_with_dc_low() {
+    let bus = bus();
+    let interface = SPIDisplayInterface::new(
+        MockSpi(bus.clone()),
+        MockDcPin(bus.clone()),
+        MockCsPin(bus.clone()),
+    );
+    let mut display = ST7567S::new(interface);
+
+    display.set_contrast(0x20).unwrap();
+    display.set_inverted(true).unwrap();
+
+    assert_eq!(
+        take_transfers(&bus),
+        [command(0x81), command(0x20), command(0xa7)]
+    );
+    assert!(bus.borrow().cs, "CS must be released after the transfer");
+}
+
+#[test]
+fn display_data_is_sent_with_dc_high() {
+    let bus = bus();
+    let interface = SPIDisplayInterface::new(
+        MockSpi(bus.clone()),
+        MockDcPin(bus.clone()),
+        MockCsPin(bus.clone()),
+    );
+    let mut display = ST7567S::new(interface);
+
+    let buffer = [0xa5; 128 * 64 / 8];
+    display.bounded_draw(&buffer, (0, 8), (127, 15)).unwrap();
+
+    let transfers = take_transfers(&bus);
+    assert_eq!(
+        transfers[..3],
+        [command(0xb1), command(0x00), command(0x10)]
+    );
+    assert_eq!(transfers[3..], [data(0xa5); 128]);
+    assert!(bus.borrow().cs, "CS must be released after the transfer");
+}
+
+#[test]
+fn init_sends_only_page_contents_as_data() {
+    let bus = bus();
+    let interface = SPIDisplayInterface::new(
+        MockSpi(bus.clone()),
+        MockDcPin(bus.clone()),
+        MockCsPin(bus.clone()),
+    );
+    let mut display = ST7567S::new(interface);
+
+    display.init().unwrap();
+
+    let transfers = take_transfers(&bus);
+    assert!(transfers.iter().all(|transfer| !transfer.cs));
+    assert_eq!(
+        transfers.iter().filter(|transfer| transfer.dc).count(),
+        132 * 9
+    );
+    assert!(transfers
+        .iter()
+        .filter(|transfer| transfer.dc)
+        .all(|transfer| transfer.byte == 0));
+    assert_eq!(transfers.last(), Some(&command(0xaf)));
+}
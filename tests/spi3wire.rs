@@ -0,0 +1,182 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+use st7567s::interface::{BitBangSPI3WireInterface, SPI3WireInterface};
+
+/// Bus activity in the order it happened
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Event {
+    /// CS pin level change
+    Cs(bool),
+    /// Words written to the SPI peripheral in a single call
+    Words(Vec<u16>),
+    /// SDA level latched on the rising edge of SCL
+    Bit(bool),
+}
+
+#[derive(Default)]
+struct BusState {
+    scl: bool,
+    sda: bool,
+    events: Vec<Event>,
+}
+
+type Bus = Rc<RefCell<BusState>>;
+
+fn take_events(bus: &Bus) -> Vec<Event> {
+    core::mem::take(&mut bus.borrow_mut().events)
+}
+
+struct MockSpi(Bus);
+
+impl embedded_hal::blocking::spi::Write<u16> for MockSpi {
+    type Error = ();
+
+    fn write(&mut self, words: &[u16]) -> Result<(), ()> {
+        self.0
+            .borrow_mut()
+            .events
+            .push(Event::Words(words.to_vec()));
+        Ok(())
+    }
+}
+
+struct MockCsPin(Bus);
+
+impl embedded_hal::digital::v2::OutputPin for MockCsPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().events.push(Event::Cs(false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().events.push(Event::Cs(true));
+        Ok(())
+    }
+}
+
+struct MockSclPin(Bus);
+
+impl embedded_hal::digital::v2::OutputPin for MockSclPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().scl = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        let mut bus = self.0.borrow_mut();
+        if !bus.scl {
+            let sda = bus.sda;
+            bus.events.push(Event::Bit(sda));
+        }
+        bus.scl = true;
+        Ok(())
+    }
+}
+
+struct MockSdaPin(Bus);
+
+impl embedded_hal::digital::v2::OutputPin for MockSdaPin {
+    type Error = ();
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().sda = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.0.borrow_mut().sda = true;
+        Ok(())
+    }
+}
+
+fn spi_interface(bus: &Bus) -> SPI3WireInterface<MockSpi, MockCsPin> {
+    SPI3WireInterface::new(MockSpi(bus.clone()), MockCsPin(bus.clone()))
+}
+
+fn bit_bang_interface(bus: &Bus) -> BitBangSPI3WireInterface<MockSclPin, MockSdaPin, MockCsPin> {
+    BitBangSPI3WireInterface::new(
+        MockSclPin(bus.clone()),
+        MockSdaPin(bus.clone()),
+        MockCsPin(bus.clone()),
+    )
+}
+
+/// Bits of a 9-bit word, A0 bit first
+fn bits(a0: bool, byte: u8) -> impl Iterator<Item = Event> {
+    core::iter::once(a0)
+        .chain((0..8).rev().map(move |bit| byte & (1 << bit) != 0))
+        .map(Event::Bit)
+}
+
+#[test]
+fn words_start_with_a0_bit() {
+    let bus = Bus::default();
+    let mut interface = spi_interface(&bus);
+
+    interface.send_commands(DataFormat::U8(&[0xa5])).unwrap();
+    interface.send_data(DataFormat::U8(&[0xa5])).unwrap();
+
+    assert_eq!(
+        take_events(&bus),
+        [
+            Event::Cs(false),
+            Event::Words(vec![0x0a5]),
+            Event::Cs(true),
+            Event::Cs(false),
+            Event::Words(vec![0x1a5]),
+            Event::Cs(true),
+        ]
+    );
+}
+
+#[test]
+fn words_are_written_in_chunks_of_32() {
+    let bus = Bus::default();
+    let mut interface = spi_interface(&bus);
+
+    interface.send_data(DataFormat::U8(&[0x01; 32])).unwrap();
+    assert_eq!(
+        take_events(&bus),
+        [
+            Event::Cs(false),
+            Event::Words(vec![0x101; 32]),
+            Event::Cs(true),
+        ]
+    );
+
+    interface.send_data(DataFormat::U8(&[0x01; 33])).unwrap();
+    assert_eq!(
+        take_events(&bus),
+        [
+            Event::Cs(false),
+            Event::Words(vec![0x101; 32]),
+            Event::Words(vec![0x101]),
+            Event::Cs(true),
+        ]
+    );
+}
+
+#[test]
+fn bit_bang_sends_a0_bit_then_byte_msb_first() {
+    let bus = Bus::default();
+    let mut interface = bit_bang_interface(&bus);
+
+    interface.send_data(DataFormat::U8(&[0xa5])).unwrap();
+    interface
+        .send_commands(DataFormat::U8(&[0x01, 0x80]))
+        .unwrap();
+
+    let mut expected = vec![Event::Cs(false)];
+    expected.extend(bits(true, 0xa5));
+    expected.extend([Event::Cs(true), Event::Cs(false)]);
+    expected.extend(bits(false, 0x01));
+    expected.extend(bits(false, 0x80));
+    expected.push(Event::Cs(true));
+    assert_eq!(take_events(&bus), expected);
+}